@@ -0,0 +1,701 @@
+/*
+ * Copyright (C) 2024 Polkadot Blockchain Academy
+ *  See the LICENSE.md file distributed with this work for additional
+ *  information regarding copyright ownership.
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+use std::collections::VecDeque;
+
+use urandom::rng::Xoshiro256;
+use urandom::Random;
+
+use crate::utils::{draw, threshold, Memory};
+use crate::Move::Y;
+use crate::{GameContext, GameKind, Move, Named, Round, Strategy};
+
+/// Wraps a strategy and, with probability `epsilon`, plays a uniformly random move instead of the
+/// inner strategy's move. Every round is still forwarded to the inner strategy.
+pub struct EpsilonGreedy<S: Strategy> {
+    inner: S,
+    epsilon: f32,
+    random: Random<Xoshiro256>,
+}
+
+impl<S: Strategy> EpsilonGreedy<S> {
+    /// Wrap `inner`, exploring with probability `epsilon` using an RNG seeded with `seed`
+    pub fn new(inner: S, epsilon: f32, seed: u64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&epsilon),
+            "Probability must be between 0.0 and 1.0"
+        );
+        EpsilonGreedy {
+            inner,
+            epsilon,
+            random: Xoshiro256::from_seed(seed),
+        }
+    }
+
+    /// Returns the wrapped strategy
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    fn explore(&mut self, inner_move: Move) -> Move {
+        if draw(&mut self.random) < threshold(self.epsilon as f64) {
+            Move::ALL[self.random.index(Move::ALL.len())]
+        } else {
+            inner_move
+        }
+    }
+}
+
+impl<S: Strategy> Named for EpsilonGreedy<S> {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+}
+
+impl<S: Strategy> Strategy for EpsilonGreedy<S> {
+    fn play_for_favoured_move(&mut self, favoured_move: Move) -> Move {
+        let inner_move = self.inner.play_for_favoured_move(favoured_move);
+        self.explore(inner_move)
+    }
+
+    fn play_with_context(&mut self, ctx: &GameContext, favoured_move: Move) -> Move {
+        let inner_move = self.inner.play_with_context(ctx, favoured_move);
+        self.explore(inner_move)
+    }
+
+    fn handle_last_round(&mut self, round: Round, favoured_move: Move) {
+        self.inner.handle_last_round(round, favoured_move);
+    }
+
+    fn observe_signal(&mut self, signal: Move) {
+        self.inner.observe_signal(signal);
+    }
+
+    fn on_disqualified(&mut self) {
+        self.inner.on_disqualified();
+    }
+
+    fn supported_games(&self) -> &[GameKind] {
+        self.inner.supported_games()
+    }
+
+    fn memory_snapshot(&self) -> Option<Vec<Move>> {
+        self.inner.memory_snapshot()
+    }
+
+    fn move_distribution(&self, favoured_move: Move) -> Option<[f32; 3]> {
+        let epsilon = self.epsilon;
+        self.inner
+            .move_distribution(favoured_move)
+            .map(|distribution| distribution.map(|p| (1.0 - epsilon) * p + epsilon / 3.0))
+    }
+}
+
+/// The distribution of a strategy that plays `m` for sure
+fn certain(m: Move) -> [f32; 3] {
+    let mut distribution = [0.0; 3];
+    distribution[m as usize] = 1.0;
+    distribution
+}
+
+/// Wraps a strategy and plays `Y` (defect) every `period` rounds to test whether the opponent
+/// retaliates, playing the wrapped strategy's move otherwise.
+///
+/// A probe counts as retaliated if the opponent plays `Y` in the round right after it.
+pub struct Prober<S: Strategy> {
+    inner: S,
+    period: usize,
+    rounds_played: usize,
+    probing: bool,
+    awaiting_response: bool,
+    probes: usize,
+    retaliations: usize,
+}
+
+impl<S: Strategy> Prober<S> {
+    /// Wrap `inner`, probing every `period` rounds
+    pub fn new(inner: S, period: usize) -> Self {
+        assert!(period > 0, "Probing period must be at least 1");
+        Prober {
+            inner,
+            period,
+            rounds_played: 0,
+            probing: false,
+            awaiting_response: false,
+            probes: 0,
+            retaliations: 0,
+        }
+    }
+
+    /// Returns the fraction of probes the opponent retaliated against, or `None` if no probe has
+    /// been answered yet
+    pub fn retaliation_rate(&self) -> Option<f32> {
+        if self.probes == 0 {
+            None
+        } else {
+            Some(self.retaliations as f32 / self.probes as f32)
+        }
+    }
+
+    fn probe(&mut self, inner_move: Move) -> Move {
+        self.rounds_played += 1;
+        self.probing = self.rounds_played % self.period == 0;
+        if self.probing {
+            Y
+        } else {
+            inner_move
+        }
+    }
+}
+
+impl<S: Strategy> Named for Prober<S> {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+}
+
+impl<S: Strategy> Strategy for Prober<S> {
+    fn play_for_favoured_move(&mut self, favoured_move: Move) -> Move {
+        let inner_move = self.inner.play_for_favoured_move(favoured_move);
+        self.probe(inner_move)
+    }
+
+    fn play_with_context(&mut self, ctx: &GameContext, favoured_move: Move) -> Move {
+        let inner_move = self.inner.play_with_context(ctx, favoured_move);
+        self.probe(inner_move)
+    }
+
+    fn handle_last_round(&mut self, round: Round, favoured_move: Move) {
+        if self.awaiting_response {
+            self.probes += 1;
+            if round.opponent_move == Y {
+                self.retaliations += 1;
+            }
+        }
+        self.awaiting_response = self.probing;
+        self.inner.handle_last_round(round, favoured_move);
+    }
+
+    fn observe_signal(&mut self, signal: Move) {
+        self.inner.observe_signal(signal);
+    }
+
+    fn on_disqualified(&mut self) {
+        self.inner.on_disqualified();
+    }
+
+    fn supported_games(&self) -> &[GameKind] {
+        self.inner.supported_games()
+    }
+
+    fn memory_snapshot(&self) -> Option<Vec<Move>> {
+        self.inner.memory_snapshot()
+    }
+
+    fn move_distribution(&self, favoured_move: Move) -> Option<[f32; 3]> {
+        if (self.rounds_played + 1) % self.period == 0 {
+            Some(certain(Y))
+        } else {
+            self.inner.move_distribution(favoured_move)
+        }
+    }
+}
+
+/// Debug wrapper that detects nondeterminism, e.g. from reading the clock, IO or global state.
+///
+/// Two instances of the strategy are built from the same factory and driven with identical inputs.
+/// The first instance's moves are played; every round in which the two instances disagree is
+/// counted as a divergence.
+pub struct Sandboxed<S: Strategy> {
+    primary: S,
+    shadow: S,
+    divergences: usize,
+}
+
+impl<S: Strategy> Sandboxed<S> {
+    /// Build the primary and shadow instances from `factory`
+    pub fn new(factory: impl Fn() -> S) -> Self {
+        Sandboxed {
+            primary: factory(),
+            shadow: factory(),
+            divergences: 0,
+        }
+    }
+
+    /// Returns the number of rounds in which the two instances chose different moves
+    pub fn divergences(&self) -> usize {
+        self.divergences
+    }
+
+    /// Returns whether the strategy has behaved nondeterministically
+    pub fn is_flagged(&self) -> bool {
+        self.divergences > 0
+    }
+
+    fn compare(&mut self, primary_move: Move, shadow_move: Move) -> Move {
+        if shadow_move != primary_move {
+            self.divergences += 1;
+        }
+        primary_move
+    }
+}
+
+impl<S: Strategy> Named for Sandboxed<S> {
+    fn name(&self) -> &str {
+        self.primary.name()
+    }
+}
+
+impl<S: Strategy> Strategy for Sandboxed<S> {
+    fn play_for_favoured_move(&mut self, favoured_move: Move) -> Move {
+        let primary_move = self.primary.play_for_favoured_move(favoured_move);
+        let shadow_move = self.shadow.play_for_favoured_move(favoured_move);
+        self.compare(primary_move, shadow_move)
+    }
+
+    fn play_with_context(&mut self, ctx: &GameContext, favoured_move: Move) -> Move {
+        let primary_move = self.primary.play_with_context(ctx, favoured_move);
+        let shadow_move = self.shadow.play_with_context(ctx, favoured_move);
+        self.compare(primary_move, shadow_move)
+    }
+
+    fn handle_last_round(&mut self, round: Round, favoured_move: Move) {
+        self.primary.handle_last_round(round, favoured_move);
+        self.shadow.handle_last_round(round, favoured_move);
+    }
+
+    fn observe_signal(&mut self, signal: Move) {
+        self.primary.observe_signal(signal);
+        self.shadow.observe_signal(signal);
+    }
+
+    fn on_disqualified(&mut self) {
+        self.primary.on_disqualified();
+        self.shadow.on_disqualified();
+    }
+
+    fn supported_games(&self) -> &[GameKind] {
+        self.primary.supported_games()
+    }
+
+    fn memory_snapshot(&self) -> Option<Vec<Move>> {
+        self.primary.memory_snapshot()
+    }
+
+    fn move_distribution(&self, favoured_move: Move) -> Option<[f32; 3]> {
+        self.primary.move_distribution(favoured_move)
+    }
+}
+
+/// Wraps a strategy and models a reaction lag: every round is passed to the inner strategy only
+/// after `delay` more rounds have been played.
+///
+/// The favoured move is played for the first `delay` rounds, so with a delay of `d` the inner
+/// strategy answers round `t` in round `t + d + 1` instead of `t + 1`.
+pub struct Delayed<S: Strategy> {
+    inner: S,
+    delay: usize,
+    buffer: VecDeque<Round>,
+}
+
+impl<S: Strategy> Delayed<S> {
+    /// Wrap `inner`, feeding it rounds `delay` rounds late
+    pub fn new(inner: S, delay: usize) -> Self {
+        Delayed {
+            inner,
+            delay,
+            buffer: VecDeque::with_capacity(delay + 1),
+        }
+    }
+}
+
+impl<S: Strategy> Named for Delayed<S> {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+}
+
+impl<S: Strategy> Strategy for Delayed<S> {
+    fn play_for_favoured_move(&mut self, favoured_move: Move) -> Move {
+        if self.buffer.len() < self.delay {
+            favoured_move
+        } else {
+            self.inner.play_for_favoured_move(favoured_move)
+        }
+    }
+
+    fn play_with_context(&mut self, ctx: &GameContext, favoured_move: Move) -> Move {
+        if self.buffer.len() < self.delay {
+            favoured_move
+        } else {
+            self.inner.play_with_context(ctx, favoured_move)
+        }
+    }
+
+    fn handle_last_round(&mut self, round: Round, favoured_move: Move) {
+        self.buffer.push_back(round);
+        if self.buffer.len() > self.delay {
+            let delayed = self.buffer.pop_front().unwrap();
+            self.inner.handle_last_round(delayed, favoured_move);
+        }
+    }
+
+    fn observe_signal(&mut self, signal: Move) {
+        self.inner.observe_signal(signal);
+    }
+
+    fn on_disqualified(&mut self) {
+        self.inner.on_disqualified();
+    }
+
+    fn supported_games(&self) -> &[GameKind] {
+        self.inner.supported_games()
+    }
+
+    fn memory_snapshot(&self) -> Option<Vec<Move>> {
+        self.inner.memory_snapshot()
+    }
+
+    fn move_distribution(&self, favoured_move: Move) -> Option<[f32; 3]> {
+        if self.buffer.len() < self.delay {
+            Some(certain(favoured_move))
+        } else {
+            self.inner.move_distribution(favoured_move)
+        }
+    }
+}
+
+/// Drives a strategy one move at a time from an external source, e.g. a remote opponent or a UI.
+///
+/// Call `next_move` to get the strategy's move, then `record_opponent` once the opponent's move for
+/// that round is known. The played rounds are kept as a transcript.
+pub struct LiveMatch {
+    strategy: Box<dyn Strategy>,
+    pending: Option<(Move, Move)>,
+    transcript: Vec<Round>,
+}
+
+impl LiveMatch {
+    pub fn new(strategy: Box<dyn Strategy>) -> Self {
+        LiveMatch {
+            strategy,
+            pending: None,
+            transcript: vec![],
+        }
+    }
+
+    /// Returns the strategy's move for the next round.
+    ///
+    /// Panics if the opponent's move for the previous round has not been recorded yet.
+    pub fn next_move(&mut self, favoured_move: Move) -> Move {
+        assert!(
+            self.pending.is_none(),
+            "record_opponent must be called before the next move"
+        );
+        let my_move = self.strategy.play_for_favoured_move(favoured_move);
+        self.pending = Some((my_move, favoured_move));
+        my_move
+    }
+
+    /// Records the opponent's move for the current round and passes the round to the strategy.
+    ///
+    /// Panics if `next_move` has not been called for this round.
+    pub fn record_opponent(&mut self, opponent_move: Move) {
+        let (my_move, favoured_move) = self
+            .pending
+            .take()
+            .expect("next_move must be called before record_opponent");
+        let round = Round::of(my_move, opponent_move);
+        self.strategy.handle_last_round(round, favoured_move);
+        self.transcript.push(round);
+    }
+
+    /// Returns the rounds played so far
+    pub fn transcript(&self) -> &[Round] {
+        &self.transcript
+    }
+}
+
+/// A strategy that plays `first` in the first round and afterwards a pure function of the last round
+pub struct Reactive<F: Fn(Round) -> Move + Sync> {
+    name: &'static str,
+    first: Move,
+    response: F,
+    memory: VecDeque<Round>,
+}
+
+impl<F: Fn(Round) -> Move + Sync> Named for Reactive<F> {
+    fn name(&self) -> &str {
+        self.name
+    }
+}
+
+impl<F: Fn(Round) -> Move + Sync> Memory<Round> for Reactive<F> {
+    fn get_memory(&mut self) -> &mut VecDeque<Round> {
+        &mut self.memory
+    }
+
+    fn max_len(&self) -> usize {
+        1
+    }
+}
+
+impl<F: Fn(Round) -> Move + Sync> Strategy for Reactive<F> {
+    fn play_for_favoured_move(&mut self, _favoured_move: Move) -> Move {
+        match self.last() {
+            Some(round) => (self.response)(round),
+            None => self.first,
+        }
+    }
+
+    fn handle_last_round(&mut self, round: Round, _favoured_move: Move) {
+        self.remember(round);
+    }
+
+    fn memory_snapshot(&self) -> Option<Vec<Move>> {
+        Some(
+            self.memory
+                .iter()
+                .map(|round| round.opponent_move)
+                .collect(),
+        )
+    }
+}
+
+/// Wraps a closure into a full `Strategy` named `name` that plays `first` in the first round and
+/// then `response(last_round)`, e.g. `reactive("TitForTat", X, |round| round.opponent_move)`.
+pub fn reactive(
+    name: &'static str,
+    first: Move,
+    response: impl Fn(Round) -> Move + Sync + 'static,
+) -> Box<dyn Strategy> {
+    Box::new(Reactive {
+        name,
+        first,
+        response,
+        memory: VecDeque::with_capacity(1),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::test_strategies::{play_against, play_match, AlwaysCooperate, TitForTat};
+    use crate::Move::{X, Z};
+    use crate::Opposite;
+
+    use super::*;
+
+    #[derive(Named)]
+    struct AlwaysZ {
+        rounds_seen: usize,
+    }
+
+    impl Strategy for AlwaysZ {
+        fn play_for_favoured_move(&mut self, _favoured_move: Move) -> Move {
+            Z
+        }
+
+        fn handle_last_round(&mut self, _round: Round, _favoured_move: Move) {
+            self.rounds_seen += 1;
+        }
+    }
+
+    #[derive(Named)]
+    struct Quitter {
+        disqualifications: usize,
+    }
+
+    impl Strategy for Quitter {
+        fn play_for_favoured_move(&mut self, favoured_move: Move) -> Move {
+            favoured_move
+        }
+
+        fn handle_last_round(&mut self, _round: Round, _favoured_move: Move) {}
+
+        fn on_disqualified(&mut self) {
+            self.disqualifications += 1;
+        }
+    }
+
+    #[test]
+    fn test_prober() {
+        let always_cooperate = || AlwaysCooperate;
+
+        let mut prober = Prober::new(always_cooperate(), 5);
+        let mut tit_for_tat = TitForTat::new();
+        let transcript = play_match(&mut prober, &mut tit_for_tat, 20);
+        assert_eq!(transcript[4].my_move, Y);
+        assert_eq!(transcript[3].my_move, X);
+        assert_eq!(prober.retaliation_rate(), Some(1.0));
+
+        let mut prober = Prober::new(always_cooperate(), 5);
+        play_match(&mut prober, &mut always_cooperate(), 20);
+        assert_eq!(prober.retaliation_rate(), Some(0.0));
+
+        assert_eq!(Prober::new(always_cooperate(), 5).retaliation_rate(), None);
+    }
+
+    #[test]
+    fn test_delayed() {
+        let opponent_moves = [X, Y, X, X, X, X];
+        let play = |strategy: &mut dyn Strategy| -> Vec<Move> {
+            play_against(strategy, &opponent_moves)
+                .iter()
+                .map(|round| round.my_move)
+                .collect()
+        };
+
+        assert_eq!(play(&mut TitForTat::new()), vec![X, X, Y, X, X, X]);
+        assert_eq!(
+            play(&mut Delayed::new(TitForTat::new(), 0)),
+            vec![X, X, Y, X, X, X]
+        );
+        assert_eq!(
+            play(&mut Delayed::new(TitForTat::new(), 1)),
+            vec![X, X, X, Y, X, X]
+        );
+        assert_eq!(
+            play(&mut Delayed::new(TitForTat::new(), 2)),
+            vec![X, X, X, X, Y, X]
+        );
+    }
+
+    static TICKS: AtomicUsize = AtomicUsize::new(0);
+
+    /// Stands in for a strategy reading the clock: its move depends on state outside of its inputs
+    #[derive(Named)]
+    struct ClockReader;
+
+    impl Strategy for ClockReader {
+        fn play_for_favoured_move(&mut self, favoured_move: Move) -> Move {
+            if TICKS.fetch_add(1, Ordering::SeqCst) % 2 == 0 {
+                favoured_move
+            } else {
+                favoured_move.opposite()
+            }
+        }
+
+        fn handle_last_round(&mut self, _round: Round, _favoured_move: Move) {}
+    }
+
+    #[test]
+    fn test_sandboxed() {
+        let mut clock_reader = Sandboxed::new(|| ClockReader);
+        play_match(&mut clock_reader, &mut AlwaysCooperate, 10);
+        assert!(clock_reader.is_flagged());
+
+        let mut pure = Sandboxed::new(TitForTat::new);
+        play_match(&mut pure, &mut clock_reader, 10);
+        assert!(!pure.is_flagged());
+        assert_eq!(pure.divergences(), 0);
+    }
+
+    #[test]
+    fn test_live_match() {
+        let mut live = LiveMatch::new(Box::new(TitForTat::new()));
+        let mut remote = Prober::new(AlwaysCooperate, 3);
+        for _ in 0..9 {
+            let my_move = live.next_move(X);
+            let remote_move = remote.play_for_favoured_move(X);
+            remote.handle_last_round(Round::of(remote_move, my_move), X);
+            live.record_opponent(remote_move);
+        }
+
+        let expected = play_match(
+            &mut TitForTat::new(),
+            &mut Prober::new(AlwaysCooperate, 3),
+            9,
+        );
+        assert_eq!(live.transcript(), expected.as_slice());
+    }
+
+    #[test]
+    #[should_panic(expected = "next_move must be called before record_opponent")]
+    fn test_live_match_out_of_order() {
+        LiveMatch::new(Box::new(AlwaysCooperate)).record_opponent(X);
+    }
+
+    #[test]
+    fn test_epsilon_greedy() {
+        let rounds = 10_000;
+        let mut strategy = EpsilonGreedy::new(AlwaysZ { rounds_seen: 0 }, 0.3, 42);
+        let mut deviations = 0;
+        for _ in 0..rounds {
+            let m = strategy.play_for_favoured_move(X);
+            if m != Z {
+                deviations += 1;
+            }
+            strategy.handle_last_round(Round::of(m, X), X);
+        }
+
+        // Exploration picks a uniformly random move, so only 2/3 of explorations deviate from Z
+        let deviation_rate = deviations as f32 / rounds as f32;
+        assert!(
+            (deviation_rate - 0.2).abs() < 0.02,
+            "deviation rate: {}",
+            deviation_rate
+        );
+        assert_eq!(strategy.inner().rounds_seen, rounds);
+        assert_eq!(strategy.name(), "AlwaysZ");
+    }
+
+    #[test]
+    fn test_reactive_tit_for_tat() {
+        let mut tit_for_tat = reactive("TitForTat", X, |round| round.opponent_move);
+        let mut played = vec![];
+        for opponent_move in [Y, Y, X, Z, X] {
+            let m = tit_for_tat.play_for_favoured_move(X);
+            played.push(m);
+            tit_for_tat.handle_last_round(Round::of(m, opponent_move), X);
+        }
+        assert_eq!(played, vec![X, Y, Y, X, Z]);
+        assert_eq!(tit_for_tat.name(), "TitForTat");
+    }
+
+    #[test]
+    fn test_memory_snapshot() {
+        let mut tit_for_tat = EpsilonGreedy::new(TitForTat::new(), 0.0, 7);
+        assert_eq!(tit_for_tat.memory_snapshot(), Some(vec![]));
+        for opponent_move in [Y, Z, X] {
+            let m = tit_for_tat.play_for_favoured_move(X);
+            tit_for_tat.handle_last_round(Round::of(m, opponent_move), X);
+            assert_eq!(tit_for_tat.memory_snapshot(), Some(vec![opponent_move]));
+        }
+
+        let mut reactive_tit_for_tat = reactive("TitForTat", X, |round| round.opponent_move);
+        reactive_tit_for_tat.handle_last_round(Round::of(X, Z), X);
+        assert_eq!(reactive_tit_for_tat.memory_snapshot(), Some(vec![Z]));
+
+        assert_eq!(AlwaysCooperate.memory_snapshot(), None);
+    }
+
+    #[test]
+    fn test_on_disqualified() {
+        let quitter = || Quitter {
+            disqualifications: 0,
+        };
+
+        let mut wrapped = Delayed::new(Prober::new(EpsilonGreedy::new(quitter(), 0.0, 7), 3), 1);
+        wrapped.on_disqualified();
+        assert_eq!(wrapped.inner.inner.inner().disqualifications, 1);
+
+        let mut sandboxed = Sandboxed::new(quitter);
+        sandboxed.on_disqualified();
+        assert_eq!(sandboxed.primary.disqualifications, 1);
+        assert_eq!(sandboxed.shadow.disqualifications, 1);
+    }
+}
@@ -0,0 +1,567 @@
+/*
+ * Copyright (C) 2024 Polkadot Blockchain Academy
+ *  See the LICENSE.md file distributed with this work for additional
+ *  information regarding copyright ownership.
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+use std::collections::HashSet;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+use urandom::rng::Xoshiro256;
+
+use crate::utils::RandomMove;
+use crate::Move::{X, Y};
+use crate::{GameKind, Move, OwnedStrategy, Round, Strategy};
+
+/// Re-drives `strategy` with the opponent moves recorded in `rounds` and checks that it reproduces
+/// every recorded `my_move`.
+///
+/// Strategies relying on randomness will only verify if they are seeded identically to the run
+/// that produced the transcript.
+pub fn verify_transcript(
+    strategy: &mut dyn Strategy,
+    rounds: &[Round],
+    favoured_move: Move,
+) -> bool {
+    first_divergence(strategy, rounds, favoured_move).is_none()
+}
+
+/// Replays `expected` against `strategy`, feeding it the recorded opponent moves, and returns the
+/// index of the first round in which its move differs from the recorded `my_move`, or `None` if it
+/// matches throughout.
+pub fn first_divergence(
+    strategy: &mut dyn Strategy,
+    expected: &[Round],
+    favoured_move: Move,
+) -> Option<usize> {
+    for (i, round) in expected.iter().enumerate() {
+        if strategy.play_for_favoured_move(favoured_move) != round.my_move {
+            return Some(i);
+        }
+        strategy.handle_last_round(*round, favoured_move);
+    }
+    None
+}
+
+/// Measures how many moves per second `strategy` can play.
+///
+/// Drives `play_for_favoured_move` and `handle_last_round` in a tight loop for `rounds` rounds,
+/// with the opponent cycling through `X`, `Y` and `Z`, and returns `rounds` divided by the
+/// elapsed time.
+pub fn throughput(strategy: &mut dyn Strategy, rounds: usize, favoured_move: Move) -> f64 {
+    let opponent_moves = Move::ALL;
+    let start_time = Instant::now();
+    for i in 0..rounds {
+        let my_move = strategy.play_for_favoured_move(favoured_move);
+        let round = Round::of(my_move, opponent_moves[i % opponent_moves.len()]);
+        strategy.handle_last_round(round, favoured_move);
+    }
+    // Clamp to the timer resolution so very fast runs don't divide by zero
+    let elapsed = start_time.elapsed().max(Duration::from_nanos(1));
+    rounds as f64 / elapsed.as_secs_f64()
+}
+
+/// Drives `a` and `b` with identical opponent moves and returns whether they played the same move in
+/// every round, e.g. to detect duplicate submissions under different names.
+///
+/// The opponent plays a battery of `probe_rounds`-long blocks one after the other: all `X`, all
+/// `Y`, all `Z`, alternating `X`/`Y`, and uniformly random moves drawn from an RNG seeded with
+/// `seed`. Both strategies see the blocks as one continuous match, so they should be fresh
+/// instances. Strategies relying on their own randomness only compare equal if seeded identically.
+pub fn behaviorally_equal(
+    a: &mut dyn Strategy,
+    b: &mut dyn Strategy,
+    probe_rounds: usize,
+    seed: u64,
+) -> bool {
+    let mut random = RandomMove::with_rng(1.0 / 3.0, 1.0 / 3.0, Xoshiro256::from_seed(seed));
+    let mut opponent_moves: Vec<Move> = vec![];
+    for fixed in Move::all() {
+        opponent_moves.extend(std::iter::repeat_n(fixed, probe_rounds));
+    }
+    opponent_moves.extend((0..probe_rounds).map(|i| [X, Y][i % 2]));
+    opponent_moves.extend((0..probe_rounds).map(|_| random.get()));
+
+    for opponent_move in opponent_moves {
+        let a_move = a.play_for_favoured_move(X);
+        let b_move = b.play_for_favoured_move(X);
+        if a_move != b_move {
+            return false;
+        }
+        a.handle_last_round(Round::of(a_move, opponent_move), X);
+        b.handle_last_round(Round::of(b_move, opponent_move), X);
+    }
+    true
+}
+
+/// Returns whether this side of the transcript is "nice", i.e. it never played `Y` (defect) unless
+/// the opponent had already played `Y` in an earlier round.
+pub fn is_nice(rounds: &[Round]) -> bool {
+    let mut opponent_defected = false;
+    for round in rounds {
+        if round.my_move == Y && !opponent_defected {
+            return false;
+        }
+        if round.opponent_move == Y {
+            opponent_defected = true;
+        }
+    }
+    true
+}
+
+/// Returns the fraction of aligned rounds in which both transcripts played the same `my_move`,
+/// i.e. one minus the normalized Hamming distance of the two move sequences.
+///
+/// Rounds past the end of the shorter transcript count as different. Two empty transcripts are
+/// considered identical.
+pub fn move_similarity(a: &[Round], b: &[Round]) -> f32 {
+    let len = a.len().max(b.len());
+    if len == 0 {
+        return 1.0;
+    }
+    let same = a
+        .iter()
+        .zip(b)
+        .filter(|(a, b)| a.my_move == b.my_move)
+        .count();
+    same as f32 / len as f32
+}
+
+/// The behaviour of an opponent as observed in a transcript, see `classify_opponent`
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum OpponentClass {
+    /// Always played `X`
+    AlwaysCooperate,
+    /// Always played `Y`
+    AlwaysDefect,
+    /// Mostly repeated this side's previous move, e.g. Tit-for-Tat
+    Reciprocator,
+    /// Hard to predict from its own previous move, see `RANDOM_ENTROPY_THRESHOLD`
+    Random,
+    /// None of the above, e.g. always playing `Z` or a fixed cycle of moves
+    Other,
+}
+
+/// The fraction of rounds in which a `Reciprocator` must have copied this side's previous move
+const RECIPROCITY_THRESHOLD: f32 = 0.9;
+
+/// The entropy in bits of the opponent's move given its previous move above which it is `Random`.
+/// Uniformly random moves approach `log2(3) ≈ 1.58` bits, while a fixed move or cycle has 0.
+const RANDOM_ENTROPY_THRESHOLD: f32 = 1.0;
+
+/// Returns the entropy in bits of the opponent's move conditioned on its previous move
+fn opponent_conditional_entropy(rounds: &[Round]) -> f32 {
+    let mut counts = [[0usize; 3]; 3];
+    for pair in rounds.windows(2) {
+        counts[pair[0].opponent_move as usize][pair[1].opponent_move as usize] += 1;
+    }
+    let total = (rounds.len() - 1) as f32;
+    let mut entropy = 0.0;
+    for following in counts {
+        let context_total: usize = following.iter().sum();
+        for count in following.into_iter().filter(|count| *count > 0) {
+            let p = count as f32 / context_total as f32;
+            entropy -= context_total as f32 / total * p * p.log2();
+        }
+    }
+    entropy
+}
+
+/// Classifies the opponent's behaviour in a transcript.
+///
+/// An opponent that only played `X` or only `Y` is `AlwaysCooperate` or `AlwaysDefect`. One that
+/// answered at least 90% of this side's moves by playing the same move in the next round is a
+/// `Reciprocator`. Of the rest, an opponent whose moves are hard to predict from its own previous
+/// move is `Random` and anything else is `Other`. Transcripts too short to tell are `Other`.
+pub fn classify_opponent(rounds: &[Round]) -> OpponentClass {
+    if rounds.len() < 2 {
+        return OpponentClass::Other;
+    }
+    if rounds.iter().all(|round| round.opponent_move == X) {
+        return OpponentClass::AlwaysCooperate;
+    }
+    if rounds.iter().all(|round| round.opponent_move == Y) {
+        return OpponentClass::AlwaysDefect;
+    }
+    let reciprocated = rounds
+        .windows(2)
+        .filter(|pair| pair[1].opponent_move == pair[0].my_move)
+        .count();
+    if reciprocated as f32 / (rounds.len() - 1) as f32 >= RECIPROCITY_THRESHOLD {
+        OpponentClass::Reciprocator
+    } else if opponent_conditional_entropy(rounds) > RANDOM_ENTROPY_THRESHOLD {
+        OpponentClass::Random
+    } else {
+        OpponentClass::Other
+    }
+}
+
+/// The longest a single `play_for_favoured_move` or `handle_last_round` call may take in
+/// `validate_field`, matching the limits checked by `submit_strategy!`
+const MAX_CALL_TIME: Duration = Duration::from_millis(100);
+
+/// The number of rounds `validate_field` plays per strategy
+const PROBE_ROUNDS: usize = 9;
+
+/// A problem found by `validate_field` or `check_game`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum FieldIssue {
+    /// More than one strategy in the field has this `OwnedStrategy::id`
+    DuplicateId(String),
+    /// More than one strategy in the field is owned by a participant with this name
+    DuplicateOwner(String),
+    /// The strategy with this ID panicked during the probe rounds
+    Panicked(String),
+    /// A single call of the strategy with this ID took longer than allowed
+    TooSlow(String, Duration),
+    /// The strategy with this ID does not support the game the field is entered into
+    UnsupportedGame(String),
+}
+
+/// The result of `validate_field` or `check_game`
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct FieldReport {
+    pub issues: Vec<FieldIssue>,
+}
+
+impl FieldReport {
+    /// Returns whether no issues were found
+    pub fn is_ok(&self) -> bool {
+        self.issues.is_empty()
+    }
+
+    fn push_once(&mut self, issue: FieldIssue) {
+        if !self.issues.contains(&issue) {
+            self.issues.push(issue);
+        }
+    }
+}
+
+/// Checks a field of strategies for problems before a long run, reporting all of them at once.
+///
+/// Every strategy plays a few probe rounds against an opponent cycling through `X`, `Y` and `Z`.
+/// Panics and calls exceeding the `submit_strategy!` time limits are reported, as are strategies
+/// sharing an `OwnedStrategy::id` and owners sharing a `Participant::name`. Probing advances the
+/// strategies' state, so build a fresh field for the actual run. Panics are caught, but the default
+/// panic hook still prints them.
+///
+/// Determinism is not checked: an `OwnedStrategy` holds a single instance, so there is nothing to
+/// replay the probe rounds against. Wrap strategies in `adapters::Sandboxed` to check that.
+pub fn validate_field(strategies: &mut [OwnedStrategy]) -> FieldReport {
+    let mut report = FieldReport::default();
+
+    let mut seen_ids = HashSet::new();
+    let mut seen_owners = HashSet::new();
+    for strategy in strategies.iter() {
+        let id = strategy.id();
+        if !seen_ids.insert(id.clone()) {
+            report.push_once(FieldIssue::DuplicateId(id));
+        }
+        if !seen_owners.insert(strategy.owner.name) {
+            report.push_once(FieldIssue::DuplicateOwner(strategy.owner.name.to_string()));
+        }
+    }
+
+    for strategy in strategies.iter() {
+        let id = strategy.id();
+        let probe = catch_unwind(AssertUnwindSafe(|| {
+            let mut slowest = Duration::ZERO;
+            let mut player = strategy.strategy.borrow_mut();
+            for opponent_move in Move::ALL.into_iter().cycle().take(PROBE_ROUNDS) {
+                let start_time = Instant::now();
+                let my_move = player.play_for_favoured_move(X);
+                slowest = slowest.max(start_time.elapsed());
+
+                let start_time = Instant::now();
+                player.handle_last_round(Round::of(my_move, opponent_move), X);
+                slowest = slowest.max(start_time.elapsed());
+            }
+            slowest
+        }));
+        match probe {
+            Err(_) => report.issues.push(FieldIssue::Panicked(id)),
+            Ok(slowest) if slowest >= MAX_CALL_TIME => {
+                report.issues.push(FieldIssue::TooSlow(id, slowest))
+            }
+            Ok(_) => {}
+        }
+    }
+
+    report
+}
+
+/// Checks that every strategy in the field lists `game` in its `Strategy::supported_games`,
+/// reporting an `UnsupportedGame` issue for each one that does not.
+pub fn check_game(strategies: &[OwnedStrategy], game: GameKind) -> FieldReport {
+    let issues = strategies
+        .iter()
+        .filter(|strategy| !strategy.strategy.borrow().supported_games().contains(&game))
+        .map(|strategy| FieldIssue::UnsupportedGame(strategy.id()))
+        .collect();
+    FieldReport { issues }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_strategies::{play_against, TitForTat};
+    use crate::Move::Z;
+    use crate::ParticipantType::Onsite;
+    use crate::{Named, Opposite, Participant};
+
+    use super::*;
+
+    #[derive(Named)]
+    struct GrowsImpatient {
+        rounds_played: usize,
+    }
+
+    impl Strategy for GrowsImpatient {
+        fn play_for_favoured_move(&mut self, _favoured_move: Move) -> Move {
+            if self.rounds_played < 3 {
+                X
+            } else {
+                Y
+            }
+        }
+
+        fn handle_last_round(&mut self, _round: Round, _favoured_move: Move) {
+            self.rounds_played += 1;
+        }
+    }
+
+    #[derive(Named)]
+    struct Grim {
+        betrayed: bool,
+    }
+
+    impl Strategy for Grim {
+        fn play_for_favoured_move(&mut self, _favoured_move: Move) -> Move {
+            if self.betrayed {
+                Y
+            } else {
+                X
+            }
+        }
+
+        fn handle_last_round(&mut self, round: Round, _favoured_move: Move) {
+            self.betrayed |= round.opponent_move == Y;
+        }
+    }
+
+    #[test]
+    fn test_verify_transcript() {
+        let mut player = TitForTat::new();
+        let mut transcript = play_against(&mut player, &[Y, X, X, Y, Y, X]);
+
+        assert!(verify_transcript(&mut TitForTat::new(), &transcript, X));
+
+        transcript[3].my_move = transcript[3].my_move.opposite();
+        assert!(!verify_transcript(&mut TitForTat::new(), &transcript, X));
+    }
+
+    #[test]
+    fn test_throughput() {
+        let mut strategy = TitForTat::new();
+        let moves_per_second = throughput(&mut strategy, 1_000, X);
+        assert!(moves_per_second.is_finite());
+        assert!(moves_per_second > 0.0);
+    }
+
+    #[test]
+    fn test_is_nice() {
+        for opponent_moves in [[X, X, X, X], [Y, Y, X, Y], [X, Z, Y, X], [Z, Z, Z, Y]] {
+            let mut tit_for_tat = TitForTat::new();
+            assert!(is_nice(&play_against(&mut tit_for_tat, &opponent_moves)));
+        }
+
+        assert!(!is_nice(&[Round::of(Y, X), Round::of(X, X)]));
+        assert!(!is_nice(&[Round::of(X, X), Round::of(Y, Y)]));
+        assert!(is_nice(&[]));
+    }
+
+    #[test]
+    fn test_move_similarity() {
+        let mut tit_for_tat = TitForTat::new();
+        let transcript = play_against(&mut tit_for_tat, &[Y, X, Y, Y, X]);
+        assert_eq!(move_similarity(&transcript, &transcript), 1.0);
+
+        let opposite: Vec<Round> = transcript
+            .iter()
+            .map(|round| Round::of(round.my_move.opposite(), round.opponent_move))
+            .collect();
+        assert_eq!(move_similarity(&transcript, &opposite), 0.0);
+
+        assert_eq!(move_similarity(&transcript[..4], &transcript), 0.8);
+        assert_eq!(move_similarity(&[], &[]), 1.0);
+    }
+
+    #[test]
+    fn test_first_divergence() {
+        let always_x = [Round::of(X, Y); 6];
+        let mut strategy = GrowsImpatient { rounds_played: 0 };
+        assert_eq!(first_divergence(&mut strategy, &always_x, X), Some(3));
+
+        let mut strategy = GrowsImpatient { rounds_played: 0 };
+        assert_eq!(first_divergence(&mut strategy, &always_x[..3], X), None);
+    }
+
+    #[test]
+    fn test_behaviorally_equal() {
+        assert!(behaviorally_equal(
+            &mut TitForTat::new(),
+            &mut TitForTat::new(),
+            10,
+            1
+        ));
+        assert!(!behaviorally_equal(
+            &mut TitForTat::new(),
+            &mut Grim { betrayed: false },
+            10,
+            1
+        ));
+        assert!(!behaviorally_equal(
+            &mut TitForTat::new(),
+            &mut GrowsImpatient { rounds_played: 0 },
+            10,
+            1
+        ));
+    }
+
+    #[derive(Named)]
+    struct PanicsOnDefection;
+
+    impl Strategy for PanicsOnDefection {
+        fn play_for_favoured_move(&mut self, favoured_move: Move) -> Move {
+            favoured_move
+        }
+
+        fn handle_last_round(&mut self, round: Round, _favoured_move: Move) {
+            assert_ne!(round.opponent_move, Y, "did not expect a defection");
+        }
+    }
+
+    #[test]
+    fn test_validate_field() {
+        let owner = |name| Participant::new(Onsite, name, name);
+
+        let mut field = vec![
+            OwnedStrategy::build(owner("alice"), TitForTat::new()),
+            OwnedStrategy::build(owner("bob"), TitForTat::new()),
+            OwnedStrategy::build(owner("carol"), PanicsOnDefection),
+            OwnedStrategy::build(owner("dave"), GrowsImpatient { rounds_played: 0 }),
+            OwnedStrategy::build(owner("dave"), GrowsImpatient { rounds_played: 0 }),
+            OwnedStrategy::build(owner("dave"), GrowsImpatient { rounds_played: 0 }),
+            OwnedStrategy::build(owner("eve"), TitForTat::new()),
+            OwnedStrategy::build(owner("eve"), Grim { betrayed: false }),
+        ];
+        let report = validate_field(&mut field);
+        assert!(!report.is_ok());
+        assert_eq!(
+            report.issues,
+            vec![
+                FieldIssue::DuplicateId(field[3].id()),
+                FieldIssue::DuplicateOwner("dave".to_string()),
+                FieldIssue::DuplicateOwner("eve".to_string()),
+                FieldIssue::Panicked(field[2].id()),
+            ]
+        );
+
+        assert!(validate_field(&mut field[..2]).is_ok());
+    }
+
+    #[derive(Named)]
+    struct RockPaperScissorsOnly;
+
+    impl Strategy for RockPaperScissorsOnly {
+        fn play_for_favoured_move(&mut self, _favoured_move: Move) -> Move {
+            Z
+        }
+
+        fn handle_last_round(&mut self, _round: Round, _favoured_move: Move) {}
+
+        fn supported_games(&self) -> &[GameKind] {
+            &[GameKind::RockPaperScissors]
+        }
+    }
+
+    #[test]
+    fn test_check_game() {
+        let owner = |name| Participant::new(Onsite, name, name);
+
+        let field = vec![
+            OwnedStrategy::build(owner("alice"), TitForTat::new()),
+            OwnedStrategy::build(owner("bob"), RockPaperScissorsOnly),
+        ];
+        let report = check_game(&field, GameKind::PrisonersDilemma);
+        assert_eq!(
+            report.issues,
+            vec![FieldIssue::UnsupportedGame(field[1].id())]
+        );
+
+        let report = check_game(&field, GameKind::RockPaperScissors);
+        assert_eq!(
+            report.issues,
+            vec![FieldIssue::UnsupportedGame(field[0].id())]
+        );
+
+        assert!(check_game(&field[..1], GameKind::PrisonersDilemma).is_ok());
+    }
+
+    #[test]
+    fn test_classify_opponent() {
+        let my_moves = [X, Y, Y, X, Z, X, Y, X, X, Y, Z, Z, X, Y, X, X, Y, X, Y, Y];
+
+        let opponent_transcript = |opponent_moves: &[Move]| -> Vec<Round> {
+            my_moves
+                .iter()
+                .zip(opponent_moves)
+                .map(|(&m, &o)| Round::of(m, o))
+                .collect()
+        };
+
+        assert_eq!(
+            classify_opponent(&opponent_transcript(&[X; 20])),
+            OpponentClass::AlwaysCooperate
+        );
+        assert_eq!(
+            classify_opponent(&opponent_transcript(&[Y; 20])),
+            OpponentClass::AlwaysDefect
+        );
+
+        let mut tit_for_tat = vec![X];
+        tit_for_tat.extend_from_slice(&my_moves[..19]);
+        assert_eq!(
+            classify_opponent(&opponent_transcript(&tit_for_tat)),
+            OpponentClass::Reciprocator
+        );
+
+        let mut random = RandomMove::with_rng(1.0 / 3.0, 1.0 / 3.0, Xoshiro256::from_seed(5));
+        let random_moves: Vec<Move> = (0..20).map(|_| random.get()).collect();
+        assert_eq!(
+            classify_opponent(&opponent_transcript(&random_moves)),
+            OpponentClass::Random
+        );
+
+        assert_eq!(
+            classify_opponent(&opponent_transcript(&[Z; 20])),
+            OpponentClass::Other
+        );
+        let alternating: Vec<Move> = (0..20).map(|i| [X, Y][i % 2]).collect();
+        assert_eq!(
+            classify_opponent(&opponent_transcript(&alternating)),
+            OpponentClass::Other
+        );
+
+        assert_eq!(classify_opponent(&[]), OpponentClass::Other);
+    }
+}
@@ -0,0 +1,56 @@
+/*
+ * Copyright (C) 2024 Polkadot Blockchain Academy
+ *  See the LICENSE.md file distributed with this work for additional
+ *  information regarding copyright ownership.
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! `quickcheck::Arbitrary` implementations for `Move` and `Round`, enabled by the `quickcheck` feature.
+//!
+//! These allow property testing of strategies, e.g. checking that a strategy never panics on any
+//! sequence of rounds.
+
+use quickcheck::{Arbitrary, Gen};
+
+use crate::{Move, Round};
+
+impl Arbitrary for Move {
+    fn arbitrary(g: &mut Gen) -> Self {
+        *g.choose(&Move::ALL).unwrap()
+    }
+}
+
+impl Arbitrary for Round {
+    fn arbitrary(g: &mut Gen) -> Self {
+        Round::of(Move::arbitrary(g), Move::arbitrary(g))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use quickcheck::quickcheck;
+
+    use crate::test_strategies::TitForTat;
+    use crate::Strategy;
+
+    use super::*;
+
+    quickcheck! {
+        fn prop_plays_valid_move(favoured_move: Move, rounds: Vec<Round>) -> bool {
+            let mut strategy = TitForTat::new();
+            rounds.into_iter().all(|round| {
+                let played = strategy.play_for_favoured_move(favoured_move);
+                strategy.handle_last_round(round, favoured_move);
+                Move::ALL.contains(&played)
+            })
+        }
+    }
+}
@@ -0,0 +1,273 @@
+/*
+ * Copyright (C) 2024 Polkadot Blockchain Academy
+ *  See the LICENSE.md file distributed with this work for additional
+ *  information regarding copyright ownership.
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+use std::collections::VecDeque;
+
+use urandom::rng::Xoshiro256;
+use urandom::Random;
+
+use crate::utils::{draw, favoured_or_opposite, majority_move, threshold, Memory, RandomBoolean};
+use crate::Move::{X, Z};
+use crate::{Move, Named, Round, Strategy};
+
+/// Imitates the opponent by playing the move it has played most often so far.
+///
+/// Plays the favoured move until `warmup` opponent moves have been observed, and whenever the
+/// opponent's most frequent move is tied.
+#[derive(Named)]
+pub struct Conformist {
+    warmup: usize,
+    memory: VecDeque<Move>,
+}
+
+impl Conformist {
+    /// Create a `Conformist` that plays the favoured move for the first `warmup` rounds
+    pub fn new(warmup: usize) -> Self {
+        Conformist {
+            warmup,
+            memory: VecDeque::new(),
+        }
+    }
+}
+
+impl Memory<Move> for Conformist {
+    fn get_memory(&mut self) -> &mut VecDeque<Move> {
+        &mut self.memory
+    }
+
+    /// Remembers every opponent move of the match
+    fn max_len(&self) -> usize {
+        usize::MAX
+    }
+}
+
+impl Strategy for Conformist {
+    fn play_for_favoured_move(&mut self, favoured_move: Move) -> Move {
+        if self.memory.len() < self.warmup {
+            return favoured_move;
+        }
+        majority_move(self.memory.make_contiguous()).unwrap_or(favoured_move)
+    }
+
+    fn handle_last_round(&mut self, round: Round, _favoured_move: Move) {
+        self.remember(round.opponent_move);
+    }
+
+    fn memory_snapshot(&self) -> Option<Vec<Move>> {
+        Some(self.memory.iter().copied().collect())
+    }
+}
+
+/// How strongly `FrequencyTarget` corrects the gap between its realized and target frequencies
+const FREQUENCY_GAIN: f32 = 10.0;
+
+/// Ignores the opponent and plays so that its own move frequencies converge to `targets`.
+///
+/// Each move is drawn with its target probability, corrected in proportion to how far the move's
+/// realized frequency lags behind or runs ahead of its target.
+#[derive(Named)]
+pub struct FrequencyTarget {
+    targets: [f32; 3],
+    counts: [usize; 3],
+    random: Random<Xoshiro256>,
+}
+
+impl FrequencyTarget {
+    /// Create a `FrequencyTarget` aiming for the frequencies of `[X, Y, Z]` in `targets`, drawing
+    /// from an RNG seeded with `seed`.
+    ///
+    /// Panics if a target is not between 0.0 and 1.0 or the targets do not sum to 1.0
+    pub fn new(targets: [f32; 3], seed: u64) -> Self {
+        assert!(
+            targets.iter().all(|p| (0.0..=1.0).contains(p)),
+            "Probability must be between 0.0 and 1.0"
+        );
+        assert!(
+            (targets.iter().sum::<f32>() - 1.0).abs() < 1e-3,
+            "Target frequencies must sum to 1.0"
+        );
+        FrequencyTarget {
+            targets,
+            counts: [0; 3],
+            random: Xoshiro256::from_seed(seed),
+        }
+    }
+
+    /// Returns the realized frequencies of `[X, Y, Z]` so far, or the targets before the first move
+    pub fn frequencies(&self) -> [f32; 3] {
+        let played: usize = self.counts.iter().sum();
+        if played == 0 {
+            return self.targets;
+        }
+        self.counts.map(|count| count as f32 / played as f32)
+    }
+}
+
+impl Strategy for FrequencyTarget {
+    fn play_for_favoured_move(&mut self, _favoured_move: Move) -> Move {
+        let distribution = self.move_distribution(X).unwrap();
+        let value = draw(&mut self.random);
+        let mut cumulative = 0.0;
+        let m = Move::all()
+            .find(|m| {
+                cumulative += distribution[*m as usize] as f64;
+                value < threshold(cumulative)
+            })
+            .unwrap_or(Z);
+        self.counts[m as usize] += 1;
+        m
+    }
+
+    fn handle_last_round(&mut self, _round: Round, _favoured_move: Move) {}
+
+    fn move_distribution(&self, _favoured_move: Move) -> Option<[f32; 3]> {
+        let frequencies = self.frequencies();
+        let mut distribution = [0.0; 3];
+        for i in 0..3 {
+            let error = self.targets[i] - frequencies[i];
+            distribution[i] = (self.targets[i] + FREQUENCY_GAIN * error).max(0.0);
+        }
+        let total: f32 = distribution.iter().sum();
+        if total == 0.0 {
+            return Some(self.targets);
+        }
+        Some(distribution.map(|p| p / total))
+    }
+}
+
+/// Plays the favoured move, but deviates to its `Opposite` the more the opponent exploits it.
+///
+/// The opponent exploits the favoured move when it answers it with any other move. The probability
+/// of deviating is the observed exploitation rate scaled down by `stubbornness`, so a
+/// `stubbornness` of 1.0 never deviates and 0.0 deviates exactly as often as it was exploited.
+#[derive(Named)]
+pub struct FavouredFollower {
+    stubbornness: f32,
+    favoured_rounds: usize,
+    exploited_rounds: usize,
+    random: RandomBoolean,
+}
+
+impl FavouredFollower {
+    /// Create a `FavouredFollower` with the given `stubbornness`, drawing from an RNG seeded with
+    /// `seed`.
+    ///
+    /// Panics if the stubbornness is not between 0.0 and 1.0
+    pub fn new(stubbornness: f32, seed: u64) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&stubbornness),
+            "Stubbornness must be between 0.0 and 1.0"
+        );
+        FavouredFollower {
+            stubbornness,
+            favoured_rounds: 0,
+            exploited_rounds: 0,
+            random: RandomBoolean::with_seed(0.0, seed),
+        }
+    }
+
+    /// Returns the fraction of rounds in which playing the favoured move was exploited, or 0.0 if
+    /// the favoured move has not been played yet
+    pub fn exploitation_rate(&self) -> f32 {
+        if self.favoured_rounds == 0 {
+            return 0.0;
+        }
+        self.exploited_rounds as f32 / self.favoured_rounds as f32
+    }
+
+    fn favoured_probability(&self) -> f32 {
+        1.0 - (1.0 - self.stubbornness) * self.exploitation_rate()
+    }
+}
+
+impl Strategy for FavouredFollower {
+    fn play_for_favoured_move(&mut self, favoured_move: Move) -> Move {
+        let prob = self.favoured_probability();
+        favoured_or_opposite(favoured_move, prob, &mut self.random)
+    }
+
+    fn handle_last_round(&mut self, round: Round, favoured_move: Move) {
+        if round.my_move == favoured_move {
+            self.favoured_rounds += 1;
+            if round.opponent_move != favoured_move {
+                self.exploited_rounds += 1;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::adapters::reactive;
+    use crate::test_strategies::{play_match, AlwaysCooperate};
+    use crate::utils::RandomMove;
+    use crate::Move::Y;
+
+    use super::*;
+
+    #[test]
+    fn test_conformist() {
+        let mut opponent = RandomMove::with_rng(0.7, 0.15, Xoshiro256::from_seed(42));
+        let mut conformist = Conformist::new(10);
+        let mut played = vec![];
+        for _ in 0..200 {
+            let m = conformist.play_for_favoured_move(Y);
+            played.push(m);
+            conformist.handle_last_round(Round::of(m, opponent.get()), Y);
+        }
+
+        assert!(played[..10].iter().all(|m| *m == Y));
+        assert!(played[50..].iter().all(|m| *m == X));
+    }
+
+    #[test]
+    fn test_frequency_target() {
+        let targets = [0.5, 0.3, 0.2];
+        let mut bot = FrequencyTarget::new(targets, 42);
+        for _ in 0..1000 {
+            let m = bot.play_for_favoured_move(X);
+            bot.handle_last_round(Round::of(m, Y), X);
+        }
+
+        let frequencies = bot.frequencies();
+        for (realized, target) in frequencies.iter().zip(targets) {
+            assert!(
+                (realized - target).abs() < 0.01,
+                "frequencies: {:?}",
+                frequencies
+            );
+        }
+    }
+
+    #[test]
+    fn test_favoured_follower() {
+        let mut follower = FavouredFollower::new(0.2, 42);
+        let transcript = play_match(&mut follower, &mut AlwaysCooperate, 1000);
+        assert!(transcript.iter().all(|round| round.my_move == X));
+        assert_eq!(follower.exploitation_rate(), 0.0);
+
+        let mut follower = FavouredFollower::new(0.2, 42);
+        let mut exploiter = reactive("AlwaysDefect", Y, |_| Y);
+        let transcript = play_match(&mut follower, exploiter.as_mut(), 1000);
+        let deviations = transcript.iter().filter(|round| round.my_move == Y).count();
+        assert_eq!(transcript[0].my_move, X);
+        assert_eq!(follower.exploitation_rate(), 1.0);
+        assert!(
+            (750..=850).contains(&deviations),
+            "deviations: {}",
+            deviations
+        );
+    }
+}
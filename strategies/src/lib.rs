@@ -18,8 +18,10 @@ extern crate enum_display_derive;
 
 use core::fmt::{Debug, Display};
 use std::cell::RefCell;
+use std::error::Error;
 use std::hash::*;
 use std::rc::Rc;
+use std::str::FromStr;
 pub use std::sync::{Arc, Mutex};
 
 use serde::Serialize;
@@ -30,7 +32,16 @@ pub use named::Named;
 
 use crate::Move::{X, Y};
 
+pub mod adapters;
+pub mod analysis;
+#[cfg(feature = "quickcheck")]
+pub mod arbitrary;
+pub mod classic;
+pub mod prelude;
+pub mod round_macro;
 pub mod submission_macro;
+#[cfg(test)]
+mod test_strategies;
 pub mod utils;
 
 /// This is the trait that needs to be implemented and submitted
@@ -46,6 +57,22 @@ pub trait Strategy: Named + Sync {
     /// The move that the strategy chooses to play.
     fn play_for_favoured_move(&mut self, favoured_move: Move) -> Move;
 
+    /// Determines the next move like `play_for_favoured_move`, with access to the current game state.
+    ///
+    /// By default the context is ignored and this delegates to `play_for_favoured_move`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - The current game state, such as the round index and running scores
+    /// * `favoured_move` - The strategy owner's favored move
+    ///
+    /// # Returns
+    ///
+    /// The move that the strategy chooses to play.
+    fn play_with_context(&mut self, _ctx: &GameContext, favoured_move: Move) -> Move {
+        self.play_for_favoured_move(favoured_move)
+    }
+
     /// Handles the last round of the game, taking into account the strategy owner's favored move.
     ///
     /// # Arguments
@@ -53,8 +80,89 @@ pub trait Strategy: Named + Sync {
     /// * `round` - The last round of the game
     /// * `favoured_move` - The strategy owner's favored move
     fn handle_last_round(&mut self, round: Round, favoured_move: Move);
+
+    /// Observes a public signal shared by both players before the next move, e.g. to coordinate on
+    /// a correlated equilibrium. Ignored by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `signal` - The public signal for the upcoming round
+    fn observe_signal(&mut self, _signal: Move) {}
+
+    /// Notifies the strategy that it has been disqualified, e.g. after a panic or timeout, so it can
+    /// clean up. The strategy will not be asked to play again. Does nothing by default.
+    fn on_disqualified(&mut self) {}
+
+    /// Returns the game variants the strategy is written for. Defaults to the prisoner's dilemma only.
+    ///
+    /// # Returns
+    ///
+    /// The supported game variants, which a runner can check before entering the strategy.
+    fn supported_games(&self) -> &[GameKind] {
+        &[GameKind::PrisonersDilemma]
+    }
+
+    /// Returns the probability distribution over the strategy's next move, if it can be expressed.
+    ///
+    /// # Arguments
+    ///
+    /// * `favoured_move` - The strategy owner's favored move
+    ///
+    /// # Returns
+    ///
+    /// The probabilities of playing `[X, Y, Z]`, or `None` if the strategy does not expose them.
+    fn move_distribution(&self, _favoured_move: Move) -> Option<[f32; 3]> {
+        None
+    }
+
+    /// Returns the opponent moves the strategy currently remembers, e.g. for display in a UI.
+    ///
+    /// # Returns
+    ///
+    /// The remembered opponent moves, oldest first, or `None` if the strategy does not track them.
+    fn memory_snapshot(&self) -> Option<Vec<Move>> {
+        None
+    }
+}
+
+/// The variants of the game a strategy can be written for
+#[derive(Clone, Copy, Debug, Display, Eq, PartialEq, Hash)]
+pub enum GameKind {
+    PrisonersDilemma,
+    RockPaperScissors,
+    PublicGoods,
 }
 
+/// The state of the game a strategy is playing, as seen by that strategy
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct GameContext {
+    /// The index of the round about to be played, starting at 0
+    pub round_index: usize,
+    /// The total number of rounds in the game
+    pub total_rounds: usize,
+    /// The strategy's accumulated score so far
+    pub my_score: i32,
+    /// The opponent's accumulated score so far
+    pub opponent_score: i32,
+}
+
+/// A strategy that can be constructed from a configuration, e.g. to sweep parameters in experiments.
+///
+/// Submit it with `submit_strategy!(..., from_config: MyStrategy)` to also generate
+/// `provide_strategy_with(config)`.
+pub trait FromConfig: Strategy + Sized + 'static {
+    /// The configuration the strategy is constructed from
+    type Config;
+
+    /// Construct the strategy from `config`
+    fn from_config(config: Self::Config) -> Self;
+}
+
+/// A move in the game.
+///
+/// Moves are totally ordered by declaration order: `X < Y < Z`. This ordering is part of the
+/// contract and can be relied on for deterministic tie-breaking, e.g. picking the smallest of
+/// several equally likely moves.
 #[derive(Eq, PartialEq, Clone, Copy, Debug, Hash, Named, Ord, PartialOrd, Serialize, Display)]
 pub enum Move {
     X,
@@ -62,6 +170,16 @@ pub enum Move {
     Z,
 }
 
+impl Move {
+    /// Every move, in their `Ord` order
+    pub const ALL: [Move; 3] = [X, Y, Z];
+
+    /// Returns an iterator over every move, in their `Ord` order
+    pub fn all() -> impl Iterator<Item = Move> {
+        Move::ALL.into_iter()
+    }
+}
+
 /// Return the opposite of a `Move` such that:
 ///
 /// `X` -> `Y`
@@ -84,8 +202,13 @@ impl Opposite for Move {
     }
 }
 
+/// Returns every ordered pair of moves, i.e. all 9 possible `(my_move, opponent_move)` combinations.
+pub fn all_move_pairs() -> [(Move, Move); 9] {
+    std::array::from_fn(|i| (Move::ALL[i / 3], Move::ALL[i % 3]))
+}
+
 /// The result of a round
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
 pub struct Round {
     /// The move that the participant made
     pub my_move: Move,
@@ -124,6 +247,81 @@ pub enum ParticipantType {
     Onsite,
 }
 
+impl ParticipantType {
+    /// Returns the stable numeric code of the participant type, for compact logs and storage.
+    ///
+    /// The mapping is fixed: System is 0, Remote is 1 and Onsite is 2.
+    pub fn code(&self) -> u8 {
+        match self {
+            ParticipantType::System => 0,
+            ParticipantType::Remote => 1,
+            ParticipantType::Onsite => 2,
+        }
+    }
+
+    /// Returns the participant type with the given stable code, or `None` if the code is unknown
+    pub fn from_code(code: u8) -> Option<Self> {
+        Self::try_from(code).ok()
+    }
+}
+
+/// Error returned when parsing a `ParticipantType`
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ParticipantTypeError {
+    /// The name is not one of "System", "Remote" or "Onsite"
+    UnknownName(String),
+    /// The ordinal is not one of 0 (System), 1 (Remote) or 2 (Onsite)
+    UnknownOrdinal(u8),
+}
+
+impl Display for ParticipantTypeError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ParticipantTypeError::UnknownName(name) => write!(
+                f,
+                "Unknown participant type '{}', expected System, Remote or Onsite",
+                name
+            ),
+            ParticipantTypeError::UnknownOrdinal(ordinal) => write!(
+                f,
+                "Unknown participant type ordinal {}, expected 0 (System), 1 (Remote) or 2 (Onsite)",
+                ordinal
+            ),
+        }
+    }
+}
+
+impl Error for ParticipantTypeError {}
+
+impl FromStr for ParticipantType {
+    type Err = ParticipantTypeError;
+
+    /// Parses "System", "Remote" or "Onsite", ignoring case
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "system" => Ok(ParticipantType::System),
+            "remote" => Ok(ParticipantType::Remote),
+            "onsite" => Ok(ParticipantType::Onsite),
+            _ => Err(ParticipantTypeError::UnknownName(s.to_string())),
+        }
+    }
+}
+
+impl TryFrom<u8> for ParticipantType {
+    type Error = ParticipantTypeError;
+
+    /// Converts the ordinal, which is the same as the stable `code`: 0 is System, 1 is Remote and
+    /// 2 is Onsite
+    fn try_from(ordinal: u8) -> Result<Self, Self::Error> {
+        match ordinal {
+            0 => Ok(ParticipantType::System),
+            1 => Ok(ParticipantType::Remote),
+            2 => Ok(ParticipantType::Onsite),
+            _ => Err(ParticipantTypeError::UnknownOrdinal(ordinal)),
+        }
+    }
+}
+
 impl Participant {
     /// Creates a new participant.
     pub fn new(
@@ -137,6 +335,22 @@ impl Participant {
             pub_name,
         }
     }
+
+    /// Returns a stable, unambiguous identifier combining the participant type, internal name and
+    /// public name, e.g. `Onsite:5:alice:5:Alice`. Names are length-prefixed so that separators
+    /// inside names cannot make two participants collide.
+    ///
+    /// Use this for keys in logs and results; `Display` is meant for presentation only.
+    pub fn stable_id(&self) -> String {
+        format!(
+            "{}:{}:{}:{}:{}",
+            self.participant_type,
+            self.name.len(),
+            self.name,
+            self.pub_name.len(),
+            self.pub_name
+        )
+    }
 }
 
 impl Display for Participant {
@@ -150,6 +364,10 @@ impl Display for Participant {
 }
 
 /// Struct for holding a strategy and its owner.
+///
+/// Two `OwnedStrategy` values are equal (and hash the same) only if they have the same owner and
+/// share the same strategy instance. Clones share the instance and therefore stay equal, while two
+/// separately constructed strategies with the same name (e.g. with different parameters) are distinct.
 #[derive(Clone)]
 pub struct OwnedStrategy {
     pub owner: Participant,
@@ -173,12 +391,7 @@ impl Display for OwnedStrategy {
 
 impl PartialEq<Self> for OwnedStrategy {
     fn eq(&self, other: &Self) -> bool {
-        self.owner.eq(&other.owner)
-            && self
-            .strategy
-            .borrow()
-            .name()
-            .eq(other.strategy.borrow().name())
+        self.owner.eq(&other.owner) && Rc::ptr_eq(&self.strategy, &other.strategy)
     }
 }
 
@@ -187,7 +400,7 @@ impl Eq for OwnedStrategy {}
 impl Hash for OwnedStrategy {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.owner.hash(state);
-        self.strategy.borrow().name().hash(state);
+        Rc::as_ptr(&self.strategy).hash(state);
     }
 }
 
@@ -196,9 +409,23 @@ impl OwnedStrategy {
         OwnedStrategy { owner, strategy }
     }
 
-    /// Returns the an ID for the strategy
+    /// Wraps a boxed strategy for `owner`
+    pub fn from_strategy(owner: Participant, strategy: Box<dyn Strategy>) -> Self {
+        OwnedStrategy::new(owner, Rc::new(RefCell::new(strategy)))
+    }
+
+    /// Wraps `strategy` for `owner`
+    pub fn build(owner: Participant, strategy: impl Strategy + 'static) -> Self {
+        OwnedStrategy::from_strategy(owner, Box::new(strategy))
+    }
+
+    /// Returns an ID for the strategy, made of the owner's `stable_id` and the strategy name
     pub fn id(&self) -> String {
-        self.to_string()
+        format!(
+            "{}:{}",
+            self.owner.stable_id(),
+            self.strategy.borrow().name()
+        )
     }
 }
 
@@ -210,9 +437,16 @@ pub trait Named {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::hash_map::DefaultHasher;
+    use std::collections::HashSet;
+
     use ParticipantType::Onsite;
 
     use super::*;
+    use crate::adapters::{Delayed, EpsilonGreedy, Prober, Sandboxed};
+    use crate::utils::RandomMove;
+
+    submit_strategy!(MyStrategy { moves: 0 }, Onsite, "MyStrategy", "MyStrategy");
 
     #[derive(Named)]
     struct MyStrategy {
@@ -227,13 +461,105 @@ mod tests {
             m
         }
 
-        fn handle_last_round(&mut self, round: Round, favoured_move: Move) {}
+        fn handle_last_round(&mut self, _round: Round, _favoured_move: Move) {}
+    }
+
+    #[derive(Named)]
+    struct Biased {
+        pub prob_x: f32,
+    }
+
+    impl Strategy for Biased {
+        fn play_for_favoured_move(&mut self, favoured_move: Move) -> Move {
+            if self.prob_x > 0.5 {
+                X
+            } else {
+                favoured_move
+            }
+        }
+
+        fn handle_last_round(&mut self, _round: Round, _favoured_move: Move) {}
+    }
+
+    #[derive(Named)]
+    struct Mixed {
+        random: RandomMove,
+    }
+
+    impl Strategy for Mixed {
+        fn play_for_favoured_move(&mut self, _favoured_move: Move) -> Move {
+            self.random.get()
+        }
+
+        fn handle_last_round(&mut self, _round: Round, _favoured_move: Move) {}
+
+        fn move_distribution(&self, _favoured_move: Move) -> Option<[f32; 3]> {
+            Some(self.random.distribution())
+        }
+    }
+
+    impl FromConfig for Biased {
+        type Config = f32;
+
+        fn from_config(prob_x: f32) -> Self {
+            Biased { prob_x }
+        }
+    }
+
+    mod configured {
+        use super::*;
+
+        submit_strategy!(Biased { prob_x: 0.0 }, Onsite, "Biased", "Biased", from_config: Biased);
+
+        #[test]
+        fn test_provide_strategy_with() {
+            let (participant, mut low) = provide_strategy_with(0.2);
+            let (_, mut high) = provide_strategy_with(0.8);
+            assert_eq!(participant, provide_strategy().0);
+            assert_eq!(low.play_for_favoured_move(Y), Y);
+            assert_eq!(high.play_for_favoured_move(Y), X);
+        }
+    }
+
+    #[derive(Named)]
+    struct SoreLoser;
+
+    impl Strategy for SoreLoser {
+        fn play_for_favoured_move(&mut self, favoured_move: Move) -> Move {
+            favoured_move
+        }
+
+        fn handle_last_round(&mut self, _round: Round, _favoured_move: Move) {}
+
+        fn play_with_context(&mut self, ctx: &GameContext, favoured_move: Move) -> Move {
+            if ctx.my_score < ctx.opponent_score {
+                favoured_move.opposite()
+            } else {
+                self.play_for_favoured_move(favoured_move)
+            }
+        }
+    }
+
+    #[derive(Named)]
+    struct SignalFollower {
+        signal: Option<Move>,
+    }
+
+    impl Strategy for SignalFollower {
+        fn play_for_favoured_move(&mut self, favoured_move: Move) -> Move {
+            self.signal.unwrap_or(favoured_move)
+        }
+
+        fn handle_last_round(&mut self, _round: Round, _favoured_move: Move) {}
+
+        fn observe_signal(&mut self, signal: Move) {
+            self.signal = Some(signal);
+        }
     }
 
     #[test]
     fn test_submit_strategy_macro() {
-        submit_strategy!(MyStrategy { moves: 0 }, Onsite, "MyStrategy", "MyStrategy");
-        let (participant, get_strategy) = provide_strategy();
+        let (_participant, get_strategy) = provide_strategy();
         for matchup in 0..1 {
             let s1 = Rc::new(RefCell::new(get_strategy()));
             let s2 = Rc::new(RefCell::new(get_strategy()));
@@ -246,4 +572,282 @@ mod tests {
             assert_eq!(s2.borrow_mut().play_for_favoured_move(s2_fm), X);
         }
     }
+
+    #[test]
+    fn test_owned_strategy_identity() {
+        let owner = Participant::new(Onsite, "owner", "Owner");
+        let a = OwnedStrategy::build(owner, Biased { prob_x: 0.2 });
+        let b = OwnedStrategy::build(owner, Biased { prob_x: 0.8 });
+        assert_ne!(a, b);
+
+        let a_clone = a.clone();
+        assert_eq!(a, a_clone);
+
+        let hash = |s: &OwnedStrategy| {
+            let mut hasher = DefaultHasher::new();
+            s.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&a), hash(&a_clone));
+        assert_ne!(hash(&a), hash(&b));
+
+        // Comparing and hashing must not borrow a strategy that is being played
+        let _playing = a.strategy.borrow_mut();
+        assert_eq!(a, a_clone);
+        assert_ne!(a, b);
+        assert_eq!(hash(&a), hash(&a_clone));
+    }
+
+    #[test]
+    fn test_owned_strategy_constructors() {
+        let owner = Participant::new(Onsite, "owner", "Owner");
+        let from_strategy = OwnedStrategy::from_strategy(owner, Box::new(MyStrategy { moves: 0 }));
+        let built = OwnedStrategy::build(owner, MyStrategy { moves: 0 });
+
+        for strategy in [&from_strategy, &built] {
+            let manual = OwnedStrategy::new(
+                owner,
+                Rc::new(RefCell::new(Box::new(MyStrategy { moves: 0 }))),
+            );
+            assert_eq!(strategy.owner, manual.owner);
+            assert_eq!(strategy.id(), manual.id());
+            assert_eq!(strategy.to_string(), manual.to_string());
+            for favoured_move in [X, Y, X] {
+                assert_eq!(
+                    strategy
+                        .strategy
+                        .borrow_mut()
+                        .play_for_favoured_move(favoured_move),
+                    manual
+                        .strategy
+                        .borrow_mut()
+                        .play_for_favoured_move(favoured_move)
+                );
+            }
+            // Same contents, but a separate instance
+            assert_ne!(*strategy, manual);
+        }
+    }
+
+    #[test]
+    fn test_move_distribution() {
+        let deterministic = MyStrategy { moves: 0 };
+        assert_eq!(deterministic.move_distribution(X), None);
+
+        let mixed = Mixed {
+            random: RandomMove::new(0.5, 0.25),
+        };
+        assert_eq!(mixed.move_distribution(X), Some([0.5, 0.25, 0.25]));
+    }
+
+    #[test]
+    fn test_wrapped_move_distribution() {
+        let mixed = || Mixed {
+            random: RandomMove::new(0.5, 0.25),
+        };
+
+        let exploring = EpsilonGreedy::new(mixed(), 0.3, 1);
+        let expected = [0.45, 0.275, 0.275];
+        let distribution = exploring.move_distribution(X).unwrap();
+        for (p, e) in distribution.iter().zip(expected) {
+            assert!((p - e).abs() < 1e-6, "{:?} != {:?}", distribution, expected);
+        }
+
+        let mut prober = Prober::new(mixed(), 2);
+        assert_eq!(prober.move_distribution(X), Some([0.5, 0.25, 0.25]));
+        prober.play_for_favoured_move(X);
+        assert_eq!(prober.move_distribution(X), Some([0.0, 1.0, 0.0]));
+
+        let sandboxed = Sandboxed::new(mixed);
+        assert_eq!(sandboxed.move_distribution(X), Some([0.5, 0.25, 0.25]));
+
+        let mut delayed = Delayed::new(mixed(), 1);
+        assert_eq!(delayed.move_distribution(Z), Some([0.0, 0.0, 1.0]));
+        delayed.handle_last_round(Round::of(Z, X), Z);
+        assert_eq!(delayed.move_distribution(Z), Some([0.5, 0.25, 0.25]));
+
+        assert_eq!(
+            EpsilonGreedy::new(MyStrategy { moves: 0 }, 0.3, 1).move_distribution(X),
+            None
+        );
+    }
+
+    #[test]
+    fn test_all_move_pairs() {
+        let pairs = all_move_pairs();
+        let unique: HashSet<(Move, Move)> = pairs.into_iter().collect();
+        assert_eq!(unique.len(), 9);
+        for m1 in Move::all() {
+            for m2 in Move::all() {
+                assert!(unique.contains(&(m1, m2)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_move_all() {
+        assert_eq!(Move::all().collect::<Vec<_>>(), vec![X, Y, Z]);
+
+        let mut rounds = HashSet::new();
+        for m1 in Move::all() {
+            for m2 in Move::all() {
+                rounds.insert(Round::of(m1, m2));
+            }
+        }
+        assert_eq!(rounds.len(), 9);
+        for (m1, m2) in all_move_pairs() {
+            assert!(rounds.contains(&Round::of(m1, m2)));
+        }
+    }
+
+    #[test]
+    fn test_round_hash_set() {
+        let rounds: HashSet<Round> = [Round::of(X, Y), Round::of(Y, X), Round::of(X, Y)]
+            .into_iter()
+            .collect();
+        assert_eq!(rounds.len(), 2);
+        assert!(rounds.contains(&Round::of(X, Y)));
+        assert!(!rounds.contains(&Round::of(Y, Y)));
+        assert_eq!(Round::of(Y, X), Round::of(Y, X));
+    }
+
+    #[test]
+    fn test_participant_stable_id() {
+        let first = Participant::new(ParticipantType::System, "first", "Bot");
+        let second = Participant::new(ParticipantType::System, "second", "Bot");
+        assert_eq!(first.to_string(), second.to_string());
+        assert_ne!(first.stable_id(), second.stable_id());
+        assert_eq!(first.stable_id(), "System:5:first:3:Bot");
+
+        let colon_in_name = Participant::new(Onsite, "a:1", "b");
+        let colon_in_pub_name = Participant::new(Onsite, "a", "1:b");
+        assert_ne!(colon_in_name.stable_id(), colon_in_pub_name.stable_id());
+    }
+
+    #[test]
+    fn test_play_with_context() {
+        let mut ctx = GameContext {
+            round_index: 3,
+            total_rounds: 10,
+            my_score: 9,
+            opponent_score: 4,
+        };
+        let mut sore_loser = SoreLoser;
+        assert_eq!(sore_loser.play_with_context(&ctx, X), X);
+        ctx.opponent_score = 12;
+        assert_eq!(sore_loser.play_with_context(&ctx, X), Y);
+
+        let mut context_unaware = MyStrategy { moves: 0 };
+        assert_eq!(context_unaware.play_with_context(&ctx, X), X);
+        assert_eq!(context_unaware.play_with_context(&ctx, X), Y);
+    }
+
+    #[test]
+    fn test_wrapped_play_with_context() {
+        let winning = GameContext {
+            my_score: 9,
+            opponent_score: 4,
+            ..GameContext::default()
+        };
+        let losing = GameContext {
+            my_score: 4,
+            opponent_score: 9,
+            ..GameContext::default()
+        };
+
+        let mut wrapped: Vec<Box<dyn Strategy>> = vec![
+            Box::new(EpsilonGreedy::new(SoreLoser, 0.0, 1)),
+            Box::new(Prober::new(SoreLoser, 100)),
+            Box::new(Sandboxed::new(|| SoreLoser)),
+            Box::new(Delayed::new(SoreLoser, 0)),
+        ];
+        for strategy in wrapped.iter_mut() {
+            assert_eq!(strategy.play_with_context(&winning, X), X);
+            assert_eq!(strategy.play_with_context(&losing, X), Y);
+        }
+    }
+
+    #[test]
+    fn test_move_ordering() {
+        assert!(X < Y);
+        assert!(Y < Z);
+        let mut moves = vec![Z, X, Y, X];
+        moves.sort();
+        assert_eq!(moves, vec![X, X, Y, Z]);
+        assert_eq!([Y, Z, X].iter().min(), Some(&X));
+    }
+
+    #[test]
+    fn test_participant_is_copy() {
+        fn take(participant: Participant) -> ParticipantName {
+            participant.name
+        }
+
+        let participant = Participant::new(Onsite, "alice", "Alice");
+        assert_eq!(take(participant), "alice");
+        assert_eq!(take(participant), "alice");
+        assert_eq!(participant.participant_type, Onsite);
+    }
+
+    #[test]
+    fn test_parse_participant_type() {
+        assert_eq!("System".parse(), Ok(ParticipantType::System));
+        assert_eq!("remote".parse(), Ok(ParticipantType::Remote));
+        assert_eq!("ONSITE".parse(), Ok(Onsite));
+        assert_eq!(
+            "offsite".parse::<ParticipantType>(),
+            Err(ParticipantTypeError::UnknownName("offsite".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_participant_type_try_from_ordinal() {
+        assert_eq!(ParticipantType::try_from(0), Ok(ParticipantType::System));
+        assert_eq!(ParticipantType::try_from(1), Ok(ParticipantType::Remote));
+        assert_eq!(ParticipantType::try_from(2), Ok(Onsite));
+        assert_eq!(
+            ParticipantType::try_from(3),
+            Err(ParticipantTypeError::UnknownOrdinal(3))
+        );
+    }
+
+    #[test]
+    fn test_observe_signal() {
+        let mut signals = RandomMove::with_rng(0.5, 0.5, Xoshiro256::from_seed(11));
+        let mut a = SignalFollower { signal: None };
+        let mut b = SignalFollower { signal: None };
+        for _ in 0..20 {
+            let signal = signals.get();
+            a.observe_signal(signal);
+            b.observe_signal(signal);
+            let a_move = a.play_for_favoured_move(X);
+            let b_move = b.play_for_favoured_move(Y);
+            assert_eq!(a_move, signal);
+            assert_eq!(a_move, b_move);
+        }
+
+        let mut ignores_signal = MyStrategy { moves: 0 };
+        ignores_signal.observe_signal(Z);
+        assert_eq!(ignores_signal.play_for_favoured_move(X), X);
+    }
+
+    #[test]
+    fn test_supported_games() {
+        let default = MyStrategy { moves: 0 };
+        assert_eq!(default.supported_games(), &[GameKind::PrisonersDilemma]);
+    }
+
+    #[test]
+    fn test_participant_type_code_round_trip() {
+        for participant_type in [ParticipantType::System, ParticipantType::Remote, Onsite] {
+            assert_eq!(
+                ParticipantType::from_code(participant_type.code()),
+                Some(participant_type)
+            );
+        }
+        assert_eq!(ParticipantType::System.code(), 0);
+        assert_eq!(ParticipantType::Remote.code(), 1);
+        assert_eq!(Onsite.code(), 2);
+        assert_eq!(ParticipantType::from_code(3), None);
+    }
 }
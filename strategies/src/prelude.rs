@@ -0,0 +1,84 @@
+/*
+ * Copyright (C) 2024 Polkadot Blockchain Academy
+ *  See the LICENSE.md file distributed with this work for additional
+ *  information regarding copyright ownership.
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Everything needed to write and submit a strategy.
+//!
+//! ```
+//! use strategies::prelude::*;
+//! ```
+
+pub use crate::utils::{Memory, RandomBoolean, RandomMove, VecDeque};
+pub use crate::Move::{X, Y, Z};
+pub use crate::ParticipantType::{Onsite, Remote};
+pub use crate::{
+    rounds, submit_strategy, FromConfig, GameContext, GameKind, Move, Named, Opposite,
+    OwnedStrategy, Participant, ParticipantType, Round, Strategy,
+};
+
+#[cfg(test)]
+mod tests {
+    use crate::prelude::*;
+
+    submit_strategy!(
+        Alternator {
+            memory: VecDeque::new()
+        },
+        Remote,
+        "alternator",
+        "Alternator"
+    );
+
+    #[derive(Named)]
+    struct Alternator {
+        memory: VecDeque<Move>,
+    }
+
+    impl Memory<Move> for Alternator {
+        fn get_memory(&mut self) -> &mut VecDeque<Move> {
+            &mut self.memory
+        }
+
+        fn max_len(&self) -> usize {
+            1
+        }
+    }
+
+    impl Strategy for Alternator {
+        fn play_for_favoured_move(&mut self, favoured_move: Move) -> Move {
+            match self.last() {
+                Some(m) => m.opposite(),
+                None => favoured_move,
+            }
+        }
+
+        fn handle_last_round(&mut self, round: Round, _favoured_move: Move) {
+            self.remember(round.my_move);
+        }
+    }
+
+    #[test]
+    fn test_prelude_submission() {
+        let (participant, factory) = provide_strategy();
+        assert_eq!(participant.participant_type, Remote);
+
+        let mut strategy = factory();
+        assert_eq!(strategy.name(), "Alternator");
+        for expected in [X, Y, X] {
+            let m = strategy.play_for_favoured_move(X);
+            assert_eq!(m, expected);
+            strategy.handle_last_round(Round::of(m, Y), X);
+        }
+    }
+}
@@ -0,0 +1,40 @@
+/*
+ * Copyright (C) 2024 Polkadot Blockchain Academy
+ *  See the LICENSE.md file distributed with this work for additional
+ *  information regarding copyright ownership.
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+/// Builds a `Vec<Round>` from `my_move/opponent_move` pairs, e.g. `rounds![X/Y, Y/X]` is
+/// `vec![Round::of(X, Y), Round::of(Y, X)]`.
+#[macro_export]
+macro_rules! rounds {
+    ($($my_move:ident / $opponent_move:ident),* $(,)?) => {
+        vec![$($crate::Round::of($crate::Move::$my_move, $crate::Move::$opponent_move)),*]
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Move::{X, Y, Z};
+    use crate::Round;
+
+    #[test]
+    fn test_rounds_macro() {
+        assert_eq!(
+            rounds![X / Y, Y / X, Z / Z],
+            vec![Round::of(X, Y), Round::of(Y, X), Round::of(Z, Z)]
+        );
+        assert_eq!(rounds![X / X,], vec![Round::of(X, X)]);
+        let empty: Vec<Round> = rounds![];
+        assert!(empty.is_empty());
+    }
+}
@@ -13,7 +13,6 @@
  *  limitations under the License.
  */
 
-use crate::{Move, Participant, Round, Strategy};
 #[macro_export]
 macro_rules! submit_strategy {
     ($strategy:expr, $participant_type:ident, $participant_name:literal, $participant_pub_name:literal) => {
@@ -42,9 +41,9 @@ macro_rules! submit_strategy {
             fn test_strategy_time() {
                 let max_move_time = Duration::from_millis(100);
                 let max_handle_round_time = Duration::from_millis(100);
-                let strategy = OwnedStrategy::new(
+                let strategy = OwnedStrategy::build(
                     Participant::new($participant_type, $participant_name, $participant_pub_name),
-                    Rc::new(RefCell::new(Box::new($strategy))),
+                    $strategy,
                 );
 
                 let start_time = Instant::now();
@@ -57,13 +56,8 @@ macro_rules! submit_strategy {
                     max_move_time
                 );
 
-                let mut rounds = vec![];
-                for m1 in vec![X, Y, Z] {
-                    for m2 in vec![X, Y, Z] {
-                        rounds.push(Round::of(m1, m2));
-                    }
-                }
-                for round in rounds {
+                for (m1, m2) in $crate::all_move_pairs() {
+                    let round = Round::of(m1, m2);
                     let start_time = Instant::now();
                     strategy.strategy.borrow_mut().handle_last_round(round, X);
                     let elapsed = start_time.elapsed();
@@ -77,4 +71,21 @@ macro_rules! submit_strategy {
             }
         }
     };
+    ($strategy:expr, $participant_type:ident, $participant_name:literal, $participant_pub_name:literal, from_config: $strategy_type:ty) => {
+        $crate::submit_strategy!(
+            $strategy,
+            $participant_type,
+            $participant_name,
+            $participant_pub_name
+        );
+
+        pub fn provide_strategy_with(
+            config: <$strategy_type as $crate::FromConfig>::Config,
+        ) -> (Participant, Box<dyn Strategy>) {
+            (
+                Participant::new($participant_type, $participant_name, $participant_pub_name),
+                Box::new(<$strategy_type as $crate::FromConfig>::from_config(config)),
+            )
+        }
+    };
 }
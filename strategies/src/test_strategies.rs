@@ -0,0 +1,83 @@
+/*
+ * Copyright (C) 2024 Polkadot Blockchain Academy
+ *  See the LICENSE.md file distributed with this work for additional
+ *  information regarding copyright ownership.
+ *  Licensed under the Apache License, Version 2.0 (the "License");
+ *  you may not use this file except in compliance with the License.
+ *  You may obtain a copy of the License at
+ *      http://www.apache.org/licenses/LICENSE-2.0
+ *  Unless required by applicable law or agreed to in writing, software
+ *  distributed under the License is distributed on an "AS IS" BASIS,
+ *  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+ *  See the License for the specific language governing permissions and
+ *  limitations under the License.
+ */
+
+//! Strategies and helpers shared by the unit tests of several modules.
+
+use crate::Move::X;
+use crate::{Move, Named, Round, Strategy};
+
+#[derive(Named)]
+pub struct TitForTat {
+    pub last_opponent_move: Option<Move>,
+}
+
+impl TitForTat {
+    pub fn new() -> Self {
+        TitForTat {
+            last_opponent_move: None,
+        }
+    }
+}
+
+impl Strategy for TitForTat {
+    fn play_for_favoured_move(&mut self, favoured_move: Move) -> Move {
+        self.last_opponent_move.unwrap_or(favoured_move)
+    }
+
+    fn handle_last_round(&mut self, round: Round, _favoured_move: Move) {
+        self.last_opponent_move = Some(round.opponent_move);
+    }
+
+    fn memory_snapshot(&self) -> Option<Vec<Move>> {
+        Some(self.last_opponent_move.into_iter().collect())
+    }
+}
+
+#[derive(Named)]
+pub struct AlwaysCooperate;
+
+impl Strategy for AlwaysCooperate {
+    fn play_for_favoured_move(&mut self, _favoured_move: Move) -> Move {
+        X
+    }
+
+    fn handle_last_round(&mut self, _round: Round, _favoured_move: Move) {}
+}
+
+/// Plays `strategy` against the scripted `opponent_moves`, with `X` as the favoured move
+pub fn play_against(strategy: &mut dyn Strategy, opponent_moves: &[Move]) -> Vec<Round> {
+    let mut transcript = vec![];
+    for &opponent_move in opponent_moves {
+        let my_move = strategy.play_for_favoured_move(X);
+        let round = Round::of(my_move, opponent_move);
+        strategy.handle_last_round(round, X);
+        transcript.push(round);
+    }
+    transcript
+}
+
+/// Plays `a` against `b` for `rounds` rounds, with `X` as both favoured moves, and returns the
+/// transcript from `a`'s side
+pub fn play_match(a: &mut dyn Strategy, b: &mut dyn Strategy, rounds: usize) -> Vec<Round> {
+    let mut transcript = vec![];
+    for _ in 0..rounds {
+        let a_move = a.play_for_favoured_move(X);
+        let b_move = b.play_for_favoured_move(X);
+        a.handle_last_round(Round::of(a_move, b_move), X);
+        b.handle_last_round(Round::of(b_move, a_move), X);
+        transcript.push(Round::of(a_move, b_move));
+    }
+    transcript
+}
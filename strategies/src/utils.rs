@@ -14,24 +14,34 @@
  */
 
 pub use core::fmt::Debug;
+use core::fmt::{Display, Formatter};
 pub use std::collections::VecDeque;
+use std::error::Error;
+use std::io::Write;
 
 use urandom::rng::Xoshiro256;
-use urandom::Random;
+use urandom::{Random, Rng};
 
-use crate::Move;
 use crate::Move::{X, Y, Z};
+use crate::{Move, Opposite};
 
 /// Something that has a limited memory based on a `VecDeque`
 pub trait Memory<T: Copy + Debug> {
     fn get_memory(&mut self) -> &mut VecDeque<T>;
 
-    /// Remember some `T`. If the current memory is equal to the capacity, the oldest entry will drop.
+    /// The maximum number of `T` to remember.
+    ///
+    /// This is independent of the `VecDeque`'s capacity, which may be larger than requested.
+    fn max_len(&self) -> usize;
+
+    /// Remember some `T`. If the memory already holds `max_len()` entries, the oldest entry will drop.
     fn remember(&mut self, data: T) {
-        if self.get_memory().capacity() == self.get_memory().len() {
-            self.get_memory().pop_front();
+        let max_len = self.max_len();
+        let memory = self.get_memory();
+        memory.push_back(data);
+        while memory.len() > max_len {
+            memory.pop_front();
         }
-        self.get_memory().push_back(data);
     }
 
     /// Returns the last remembered `T`
@@ -39,76 +49,379 @@ pub trait Memory<T: Copy + Debug> {
         self.get_memory().back().copied()
     }
 
-    /// Returns the `n` latest remembered `T`
+    /// Returns the last remembered `T`, or `default` if nothing has been remembered yet.
+    ///
+    /// This collapses the common first-round special case, e.g. Tit-for-Tat can play
+    /// `self.last_or(favoured_move)` over a memory of opponent moves.
+    fn last_or(&mut self, default: T) -> T {
+        self.last().unwrap_or(default)
+    }
+
+    /// Returns whether nothing has been remembered yet, e.g. in the first round
+    fn is_empty(&mut self) -> bool {
+        self.get_memory().is_empty()
+    }
+
+    /// Returns the `n`th latest remembered `T`, where `last_n(0)` is the same as `last()`, or `None`
+    /// if fewer than `n + 1` entries are remembered
     fn last_n(&mut self, n: usize) -> Option<T> {
-        self.get_memory().get(n).copied()
+        let memory = self.get_memory();
+        memory
+            .len()
+            .checked_sub(1 + n)
+            .and_then(|i| memory.get(i))
+            .copied()
+    }
+}
+
+/// What to play in the very first round, before there is any history to react to.
+///
+/// Combine it with `Memory::last_or`, e.g. `self.last_or(self.first_move.resolve(favoured_move))`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FirstMove {
+    /// Play the favoured move
+    Favoured,
+    /// Play the opposite of the favoured move
+    Opposite,
+    /// Always play the given move
+    Fixed(Move),
+}
+
+impl FirstMove {
+    /// Returns the move to open with given the favoured move
+    pub fn resolve(self, favoured_move: Move) -> Move {
+        match self {
+            FirstMove::Favoured => favoured_move,
+            FirstMove::Opposite => favoured_move.opposite(),
+            FirstMove::Fixed(m) => m,
+        }
+    }
+}
+
+/// Returns the most common move in `moves`, or `None` if `moves` is empty or the top count is tied
+pub fn majority_move(moves: &[Move]) -> Option<Move> {
+    let mut counts = [0usize; 3];
+    for m in moves {
+        counts[*m as usize] += 1;
+    }
+    let max = *counts.iter().max()?;
+    let mut winners = Move::all().filter(|m| counts[*m as usize] == max);
+    match (winners.next(), winners.next()) {
+        (Some(m), None) if max > 0 => Some(m),
+        _ => None,
+    }
+}
+
+/// Returns the majority of the `k` most recent moves in `memory` (see `majority_move`).
+/// If `k` exceeds the number of remembered moves, all of them are considered.
+pub fn last_k_majority(memory: &VecDeque<Move>, k: usize) -> Option<Move> {
+    let recent: Vec<Move> = memory.iter().rev().take(k).copied().collect();
+    majority_move(&recent)
+}
+
+/// Writes a text bar chart of how often each move occurs in `moves`, one line per move, e.g.
+///
+/// ```text
+/// X | ######################################## 4
+/// Y | ########## 1
+/// Z |  0
+/// ```
+///
+/// Bars are scaled so the most common move spans 40 characters.
+pub fn print_move_histogram<W: Write>(moves: &[Move], w: &mut W) -> std::io::Result<()> {
+    const MAX_WIDTH: usize = 40;
+    let mut counts = [0usize; 3];
+    for m in moves {
+        counts[*m as usize] += 1;
+    }
+    let max = counts.iter().copied().max().unwrap_or(0).max(1);
+    for m in Move::all() {
+        let count = counts[m as usize];
+        let bar = "#".repeat(count * MAX_WIDTH / max);
+        writeln!(w, "{} | {} {}", m, bar, count)?;
     }
+    Ok(())
+}
+
+/// The maximum number of moves `pack_moves` can fit into a `u64`, since `3^40 < 2^64 < 3^41`
+pub const MAX_PACKED_MOVES: usize = 40;
+
+/// Packs up to `MAX_PACKED_MOVES` moves into a `u64`, e.g. to key a policy table on recent history.
+///
+/// Moves are encoded in base 3 (`X` = 0, `Y` = 1, `Z` = 2), first move in the least significant
+/// digit. The length is not encoded, so sequences of different lengths should not share a table,
+/// and `unpack_moves` needs to be told the length.
+///
+/// Panics if more than `MAX_PACKED_MOVES` moves are given.
+pub fn pack_moves(moves: &[Move]) -> u64 {
+    assert!(
+        moves.len() <= MAX_PACKED_MOVES,
+        "Cannot pack more than {} moves",
+        MAX_PACKED_MOVES
+    );
+    moves
+        .iter()
+        .rev()
+        .fold(0u64, |packed, m| packed * 3 + *m as u64)
+}
+
+/// Unpacks `len` moves packed by `pack_moves`
+pub fn unpack_moves(mut packed: u64, len: usize) -> Vec<Move> {
+    (0..len)
+        .map(|_| {
+            let m = Move::ALL[(packed % 3) as usize];
+            packed /= 3;
+            m
+        })
+        .collect()
+}
+
+/// How `move_distance` measures the distance between two moves
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum MoveMetric {
+    /// Moves lie on a line `X - Y - Z`: `X` and `Z` are 2 apart, neighbours are 1 apart
+    Linear,
+    /// Moves lie on a cycle as in Rock-Paper-Scissors: any two distinct moves are 1 apart
+    Cyclic,
+}
+
+/// Returns the distance between two moves under `metric`, 0 if they are equal.
+///
+/// With `MoveMetric::Linear`, moves are placed at `X` = 0, `Y` = 1, `Z` = 2 and the distance is
+/// the absolute difference. With `MoveMetric::Cyclic`, the distance is the shorter way around the
+/// cycle `X -> Y -> Z -> X`, so it is at most 1.
+pub fn move_distance(a: Move, b: Move, metric: MoveMetric) -> u8 {
+    let linear = (a as u8).abs_diff(b as u8);
+    match metric {
+        MoveMetric::Linear => linear,
+        MoveMetric::Cyclic => linear.min(3 - linear),
+    }
+}
+
+/// Shuffles `items` in place with the Fisher–Yates algorithm.
+///
+/// The result only depends on the state of `rng`, so a seeded RNG (`Xoshiro256::from_seed`) gives
+/// a reproducible permutation.
+pub fn shuffle<T, R: Rng>(items: &mut [T], rng: &mut Random<R>) {
+    for i in (1..items.len()).rev() {
+        let j = rng.index(i + 1);
+        items.swap(i, j);
+    }
+}
+
+/// Error returned when constructing a random helper from invalid probabilities
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ProbabilityError {
+    /// The probability is not between 0.0 and 1.0
+    OutOfRange(f32),
+    /// The probability of the given move is not between 0.0 and 1.0
+    MoveOutOfRange(Move, f32),
+    /// The combined probability of X and Y exceeds 1.0
+    SumExceedsOne(f32),
+}
+
+impl Display for ProbabilityError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            ProbabilityError::OutOfRange(_) => {
+                f.write_str("Probability must be between 0.0 and 1.0")
+            }
+            ProbabilityError::MoveOutOfRange(m, _) => {
+                write!(f, "Probability of {} must be between 0.0 and 1.0", m)
+            }
+            ProbabilityError::SumExceedsOne(_) => {
+                f.write_str("Combined probability of X and Y cannot exceed 1.0")
+            }
+        }
+    }
+}
+
+impl Error for ProbabilityError {}
+
+/// Converts a probability in `0.0..=1.0` into an integer threshold over the `u32` range, so that
+/// `draw(rng) < threshold` holds with that probability.
+///
+/// Sampling with integer comparisons instead of `f32` arithmetic makes the same seed produce the
+/// same draws on every platform.
+pub(crate) fn threshold(probability: f64) -> u64 {
+    ((probability * (1u64 << 32) as f64) as u64).min(1 << 32)
+}
+
+/// Draws a uniform value to compare against a `threshold`
+pub(crate) fn draw<R: Rng + ?Sized>(random: &mut Random<R>) -> u64 {
+    random.next_u32() as u64
 }
 
-pub struct RandomBoolean {
-    random: Random<Xoshiro256>,
-    probability: f32,
+/// Snapshot of the RNG state of a `RandomBoolean` or `RandomMove`.
+///
+/// `urandom` does not expose the raw `Xoshiro256` state, so the snapshot is a copy of the generator.
+#[derive(Clone, Debug)]
+pub struct RngState<R: Rng = Xoshiro256>(Random<R>);
+
+/// A random boolean that is `true` with a given probability.
+///
+/// Uses `Xoshiro256` by default; any other `urandom::Rng` can be plugged in with `with_rng`.
+pub struct RandomBoolean<R: Rng = Xoshiro256> {
+    random: Random<R>,
+    threshold: u64,
 }
 
 impl RandomBoolean {
+    /// Create a new `RandomBoolean` that is `true` with the given probability.
+    ///
+    /// Panics if the probability is invalid, see `try_new` for a non-panicking alternative.
     pub fn new(probability: f32) -> RandomBoolean {
-        assert!(
-            (0.0..=1.0).contains(&probability),
-            "Probability must be between 0.0 and 1.0"
-        );
-        RandomBoolean {
-            random: Xoshiro256::new(),
-            probability,
+        Self::with_rng(probability, Xoshiro256::new())
+    }
+
+    /// Create a new `RandomBoolean` that is `true` with the given probability.
+    /// Returns an error if the probability is not between 0.0 and 1.0
+    pub fn try_new(probability: f32) -> Result<RandomBoolean, ProbabilityError> {
+        Self::try_with_rng(probability, Xoshiro256::new())
+    }
+
+    /// Create a new `RandomBoolean` whose RNG is seeded with `seed`, for reproducible draws.
+    ///
+    /// Panics if the probability is invalid.
+    pub fn with_seed(probability: f32, seed: u64) -> RandomBoolean {
+        Self::with_rng(probability, Xoshiro256::from_seed(seed))
+    }
+}
+
+impl<R: Rng> RandomBoolean<R> {
+    /// Create a new `RandomBoolean` drawing from the given RNG.
+    ///
+    /// Panics if the probability is invalid, see `try_with_rng` for a non-panicking alternative.
+    pub fn with_rng(probability: f32, random: Random<R>) -> RandomBoolean<R> {
+        Self::try_with_rng(probability, random).unwrap_or_else(|err| panic!("{}", err))
+    }
+
+    /// Create a new `RandomBoolean` drawing from the given RNG.
+    /// Returns an error if the probability is not between 0.0 and 1.0
+    pub fn try_with_rng(
+        probability: f32,
+        random: Random<R>,
+    ) -> Result<RandomBoolean<R>, ProbabilityError> {
+        if !(0.0..=1.0).contains(&probability) {
+            return Err(ProbabilityError::OutOfRange(probability));
         }
+        Ok(RandomBoolean {
+            random,
+            threshold: threshold(probability as f64),
+        })
     }
 
     pub fn get(&mut self) -> bool {
-        let random_value: f32 = self.random.range(0f32..1f32);
-        random_value < self.probability
+        draw(&mut self.random) < self.threshold
+    }
+
+    /// Restores the RNG state from a snapshot taken with `rng_state`
+    pub fn set_rng_state(&mut self, state: RngState<R>) {
+        self.random = state.0;
     }
 }
 
-pub struct RandomMove {
-    random: Random<Xoshiro256>,
+impl<R: Rng + Clone> RandomBoolean<R> {
+    /// Returns a snapshot of the RNG state, which can be restored with `set_rng_state`
+    pub fn rng_state(&self) -> RngState<R> {
+        RngState(self.random.clone())
+    }
+}
+
+/// A random move drawn with given probabilities for `X` and `Y`, with `Z` taking the remainder.
+///
+/// Uses `Xoshiro256` by default; any other `urandom::Rng` can be plugged in with `with_rng`.
+pub struct RandomMove<R: Rng = Xoshiro256> {
+    random: Random<R>,
     prob_x: f32,
     prob_y: f32,
+    threshold_x: u64,
+    threshold_xy: u64,
 }
 
 impl RandomMove {
     /// Create a new `RandomMove` with the given probabilities for X and Y. Z would be inferred as the remainder probability.
     /// Combined probability of X and Y cannot exceed 1.0
+    ///
+    /// Panics if the probabilities are invalid, see `try_new` for a non-panicking alternative.
     pub fn new(prob_x: f32, prob_y: f32) -> RandomMove {
-        assert!(
-            (0.0..=1.0).contains(&prob_x),
-            "Probability of X must be between 0.0 and 1.0"
-        );
-        assert!(
-            (0.0..=1.0).contains(&prob_y),
-            "Probability of Y must be between 0.0 and 1.0"
-        );
-        assert!(
-            prob_x + prob_y <= 1.0,
-            "Combined probability of X and Y cannot exceed 1.0"
-        );
+        Self::with_rng(prob_x, prob_y, Xoshiro256::new())
+    }
+
+    /// Create a new `RandomMove` with the given probabilities for X and Y.
+    /// Returns an error if either probability is not between 0.0 and 1.0 or if their sum exceeds 1.0
+    pub fn try_new(prob_x: f32, prob_y: f32) -> Result<RandomMove, ProbabilityError> {
+        Self::try_with_rng(prob_x, prob_y, Xoshiro256::new())
+    }
+}
+
+impl<R: Rng> RandomMove<R> {
+    /// Create a new `RandomMove` drawing from the given RNG.
+    ///
+    /// Panics if the probabilities are invalid, see `try_with_rng` for a non-panicking alternative.
+    pub fn with_rng(prob_x: f32, prob_y: f32, random: Random<R>) -> RandomMove<R> {
+        Self::try_with_rng(prob_x, prob_y, random).unwrap_or_else(|err| panic!("{}", err))
+    }
 
-        RandomMove {
-            random: Xoshiro256::new(),
+    /// Create a new `RandomMove` drawing from the given RNG.
+    /// Returns an error if either probability is not between 0.0 and 1.0 or if their sum exceeds 1.0
+    pub fn try_with_rng(
+        prob_x: f32,
+        prob_y: f32,
+        random: Random<R>,
+    ) -> Result<RandomMove<R>, ProbabilityError> {
+        if !(0.0..=1.0).contains(&prob_x) {
+            return Err(ProbabilityError::MoveOutOfRange(X, prob_x));
+        }
+        if !(0.0..=1.0).contains(&prob_y) {
+            return Err(ProbabilityError::MoveOutOfRange(Y, prob_y));
+        }
+        if prob_x + prob_y > 1.0 {
+            return Err(ProbabilityError::SumExceedsOne(prob_x + prob_y));
+        }
+
+        Ok(RandomMove {
+            random,
             prob_x,
             prob_y,
-        }
+            threshold_x: threshold(prob_x as f64),
+            threshold_xy: threshold(prob_x as f64 + prob_y as f64),
+        })
     }
 
     pub fn get(&mut self) -> Move {
-        let random_value: f32 = self.random.range(0f32..1f32);
+        let random_value = draw(&mut self.random);
 
-        if random_value < self.prob_x {
+        if random_value < self.threshold_x {
             X
-        } else if random_value < (self.prob_x + self.prob_y) {
+        } else if random_value < self.threshold_xy {
             Y
         } else {
             Z
         }
     }
+
+    /// Restores the RNG state from a snapshot taken with `rng_state`
+    pub fn set_rng_state(&mut self, state: RngState<R>) {
+        self.random = state.0;
+    }
+
+    /// Returns the probabilities of `[X, Y, Z]`
+    pub fn distribution(&self) -> [f32; 3] {
+        // Rounding can make the remainder slightly negative when prob_x + prob_y is close to 1
+        [
+            self.prob_x,
+            self.prob_y,
+            (1.0 - self.prob_x - self.prob_y).max(0.0),
+        ]
+    }
+}
+
+impl<R: Rng + Clone> RandomMove<R> {
+    /// Returns a snapshot of the RNG state, which can be restored with `set_rng_state`
+    pub fn rng_state(&self) -> RngState<R> {
+        RngState(self.random.clone())
+    }
 }
 
 impl Default for RandomMove {
@@ -117,3 +430,316 @@ impl Default for RandomMove {
         RandomMove::new(third, third)
     }
 }
+
+/// Returns `favoured` with probability `prob`, otherwise its `Opposite`, drawing from `rng`.
+///
+/// Only the RNG of `rng` is used, its own probability is ignored. Note that `Z` is its own opposite,
+/// so a favoured `Z` is always returned.
+pub fn favoured_or_opposite<R: Rng>(favoured: Move, prob: f32, rng: &mut RandomBoolean<R>) -> Move {
+    assert!(
+        (0.0..=1.0).contains(&prob),
+        "Probability must be between 0.0 and 1.0"
+    );
+    if draw(&mut rng.random) < threshold(prob as f64) {
+        favoured
+    } else {
+        favoured.opposite()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use urandom::rng::SplitMix64;
+
+    use super::*;
+
+    struct History {
+        memory: VecDeque<Move>,
+    }
+
+    impl Memory<Move> for History {
+        fn get_memory(&mut self) -> &mut VecDeque<Move> {
+            &mut self.memory
+        }
+
+        fn max_len(&self) -> usize {
+            3
+        }
+    }
+
+    #[test]
+    fn test_memory_last_or() {
+        let mut history = History {
+            memory: VecDeque::new(),
+        };
+        assert!(history.is_empty());
+        assert_eq!(history.last_or(X), X);
+
+        history.remember(Y);
+        assert!(!history.is_empty());
+        assert_eq!(history.last_or(X), Y);
+    }
+
+    #[test]
+    fn test_memory_last_n() {
+        let mut history = History {
+            memory: VecDeque::new(),
+        };
+        assert_eq!(history.last_n(0), None);
+        assert_eq!(history.last_n(2), None);
+
+        history.remember(X);
+        history.remember(Y);
+        assert_eq!(history.last_n(0), history.last());
+        assert_eq!(history.last_n(0), Some(Y));
+        assert_eq!(history.last_n(1), Some(X));
+        assert_eq!(history.last_n(2), None);
+
+        let remembered = [X, Y, Z, Z, Y, X, Y, Y, Z, X];
+        for &m in &remembered[2..] {
+            history.remember(m);
+        }
+        for n in 0..3 {
+            assert_eq!(
+                history.last_n(n),
+                Some(remembered[remembered.len() - 1 - n])
+            );
+        }
+        assert_eq!(history.last_n(3), None);
+    }
+
+    #[test]
+    fn test_memory_max_len() {
+        let mut history = History {
+            memory: VecDeque::with_capacity(3),
+        };
+        let remembered = [X, Y, Z, Z, Y, X, Y, Y, Z, X];
+        for (i, &m) in remembered.iter().enumerate() {
+            history.remember(m);
+            assert_eq!(history.get_memory().len(), (i + 1).min(3));
+        }
+        assert_eq!(
+            history.get_memory().iter().copied().collect::<Vec<_>>(),
+            remembered[7..].to_vec()
+        );
+    }
+
+    #[test]
+    fn test_majority_move() {
+        assert_eq!(majority_move(&[X, Y, X, Z, X]), Some(X));
+        assert_eq!(majority_move(&[Z]), Some(Z));
+        assert_eq!(majority_move(&[X, Y, Y, X]), None);
+        assert_eq!(majority_move(&[X, Y, Z]), None);
+        assert_eq!(majority_move(&[]), None);
+    }
+
+    #[test]
+    fn test_last_k_majority() {
+        let memory = VecDeque::from(vec![X, X, X, Y, Y]);
+        assert_eq!(last_k_majority(&memory, 2), Some(Y));
+        assert_eq!(last_k_majority(&memory, 4), None);
+        assert_eq!(last_k_majority(&memory, 10), Some(X));
+        assert_eq!(last_k_majority(&memory, 0), None);
+    }
+
+    #[test]
+    fn test_shuffle() {
+        let original: Vec<u32> = (0..50).collect();
+
+        let mut first = original.clone();
+        shuffle(&mut first, &mut Xoshiro256::from_seed(42));
+        let mut second = original.clone();
+        shuffle(&mut second, &mut Xoshiro256::from_seed(42));
+        assert_eq!(first, second);
+        assert_ne!(first, original);
+
+        let mut sorted = first.clone();
+        sorted.sort();
+        assert_eq!(sorted, original);
+
+        let mut empty: [u32; 0] = [];
+        shuffle(&mut empty, &mut Xoshiro256::from_seed(42));
+    }
+
+    #[test]
+    fn test_print_move_histogram() {
+        let mut buffer = vec![];
+        print_move_histogram(&[X, Y, X, X, X], &mut buffer).unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0], format!("X | {} 4", "#".repeat(40)));
+        assert_eq!(lines[1], format!("Y | {} 1", "#".repeat(10)));
+        assert_eq!(lines[2], "Z |  0");
+    }
+
+    #[test]
+    fn test_move_distance() {
+        for (a, b) in crate::all_move_pairs() {
+            let linear = move_distance(a, b, MoveMetric::Linear);
+            let cyclic = move_distance(a, b, MoveMetric::Cyclic);
+            assert_eq!(linear, move_distance(b, a, MoveMetric::Linear));
+            assert_eq!(cyclic, move_distance(b, a, MoveMetric::Cyclic));
+            if a == b {
+                assert_eq!((linear, cyclic), (0, 0));
+            }
+        }
+
+        assert_eq!(move_distance(X, Y, MoveMetric::Linear), 1);
+        assert_eq!(move_distance(Y, Z, MoveMetric::Linear), 1);
+        assert_eq!(move_distance(X, Z, MoveMetric::Linear), 2);
+        assert_eq!(move_distance(X, Y, MoveMetric::Cyclic), 1);
+        assert_eq!(move_distance(Y, Z, MoveMetric::Cyclic), 1);
+        assert_eq!(move_distance(X, Z, MoveMetric::Cyclic), 1);
+    }
+
+    #[test]
+    fn test_pack_moves_round_trip() {
+        let moves: Vec<Move> = (0..MAX_PACKED_MOVES).map(|i| [Y, Z, X, Z][i % 4]).collect();
+        for len in [0, 1, 2, 7, 20, MAX_PACKED_MOVES] {
+            let packed = pack_moves(&moves[..len]);
+            assert_eq!(unpack_moves(packed, len), moves[..len].to_vec());
+        }
+
+        let all_z = [Z; MAX_PACKED_MOVES];
+        assert_eq!(
+            unpack_moves(pack_moves(&all_z), MAX_PACKED_MOVES),
+            all_z.to_vec()
+        );
+        assert_ne!(pack_moves(&[X, Y]), pack_moves(&[Y, X]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot pack more than 40 moves")]
+    fn test_pack_too_many_moves() {
+        pack_moves(&[X; MAX_PACKED_MOVES + 1]);
+    }
+
+    #[test]
+    fn test_first_move() {
+        assert_eq!(FirstMove::Favoured.resolve(X), X);
+        assert_eq!(FirstMove::Opposite.resolve(X), Y);
+        assert_eq!(FirstMove::Opposite.resolve(Z), Z);
+        assert_eq!(FirstMove::Fixed(Z).resolve(Y), Z);
+    }
+
+    #[test]
+    fn test_random_boolean_try_new() {
+        assert!(RandomBoolean::try_new(0.5).is_ok());
+        assert_eq!(
+            RandomBoolean::try_new(1.5).err(),
+            Some(ProbabilityError::OutOfRange(1.5))
+        );
+        assert_eq!(
+            RandomBoolean::try_new(-0.1).err(),
+            Some(ProbabilityError::OutOfRange(-0.1))
+        );
+    }
+
+    #[test]
+    fn test_random_move_distribution_non_negative() {
+        let distribution = RandomMove::new(0.09, 0.91).distribution();
+        assert!(distribution.iter().all(|p| *p >= 0.0));
+    }
+
+    #[test]
+    fn test_random_move_try_new() {
+        assert!(RandomMove::try_new(0.5, 0.5).is_ok());
+        assert_eq!(
+            RandomMove::try_new(-0.1, 0.5).err(),
+            Some(ProbabilityError::MoveOutOfRange(X, -0.1))
+        );
+        assert_eq!(
+            RandomMove::try_new(0.5, 1.1).err(),
+            Some(ProbabilityError::MoveOutOfRange(Y, 1.1))
+        );
+        assert_eq!(
+            RandomMove::try_new(0.75, 0.5).err(),
+            Some(ProbabilityError::SumExceedsOne(1.25))
+        );
+    }
+
+    #[test]
+    fn test_rng_state_restore() {
+        let mut random_boolean = RandomBoolean::new(0.5);
+        let state = random_boolean.rng_state();
+        let first: Vec<bool> = (0..32).map(|_| random_boolean.get()).collect();
+        random_boolean.set_rng_state(state);
+        let second: Vec<bool> = (0..32).map(|_| random_boolean.get()).collect();
+        assert_eq!(first, second);
+
+        let mut random_move = RandomMove::default();
+        let state = random_move.rng_state();
+        let first: Vec<Move> = (0..32).map(|_| random_move.get()).collect();
+        random_move.set_rng_state(state);
+        let second: Vec<Move> = (0..32).map(|_| random_move.get()).collect();
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_favoured_or_opposite() {
+        let mut rng = RandomBoolean::with_seed(0.5, 7);
+        let samples = 10_000;
+        let moves: Vec<Move> = (0..samples)
+            .map(|_| favoured_or_opposite(X, 0.7, &mut rng))
+            .collect();
+        let favoured = moves.iter().filter(|&&m| m == X).count();
+        let opposite = moves.iter().filter(|&&m| m == Y).count();
+        assert_eq!(favoured + opposite, samples);
+        let favoured_rate = favoured as f32 / samples as f32;
+        assert!(
+            (favoured_rate - 0.7).abs() < 0.02,
+            "favoured rate: {}",
+            favoured_rate
+        );
+
+        for _ in 0..100 {
+            assert_eq!(favoured_or_opposite(Z, 0.5, &mut rng), Z);
+        }
+    }
+
+    #[test]
+    fn test_seeded_draws_are_pinned() {
+        let mut random_move = RandomMove::with_rng(0.5, 0.25, Xoshiro256::from_seed(42));
+        let moves: Vec<Move> = (0..12).map(|_| random_move.get()).collect();
+        assert_eq!(moves, vec![X, X, X, X, X, X, X, X, Z, Y, X, Z]);
+
+        let mut random_boolean = RandomBoolean::with_seed(0.3, 42);
+        let booleans: Vec<bool> = (0..12).map(|_| random_boolean.get()).collect();
+        assert_eq!(
+            booleans,
+            vec![true, false, true, false, true, false, true, true, false, false, true, false]
+        );
+    }
+
+    #[test]
+    fn test_threshold_edges() {
+        assert_eq!(threshold(0.0), 0);
+        assert_eq!(threshold(0.5), 1 << 31);
+        assert_eq!(threshold(1.0), 1 << 32);
+        assert!(RandomBoolean::new(1.0).get());
+        assert!(!RandomBoolean::new(0.0).get());
+        assert_eq!(RandomMove::new(0.0, 0.0).get(), Z);
+        assert_eq!(RandomMove::new(0.0, 1.0).get(), Y);
+    }
+
+    #[test]
+    fn test_alternate_rng() {
+        let mut random_move = RandomMove::with_rng(0.5, 0.25, SplitMix64::from_seed(3));
+        let moves: Vec<Move> = (0..100).map(|_| random_move.get()).collect();
+        assert!(moves.contains(&X));
+
+        let mut random_boolean = RandomBoolean::with_rng(1.0, SplitMix64::from_seed(3));
+        assert!(random_boolean.get());
+
+        let mut replay = RandomMove::with_rng(0.5, 0.25, SplitMix64::from_seed(3));
+        let replayed: Vec<Move> = (0..100).map(|_| replay.get()).collect();
+        assert_eq!(moves, replayed);
+    }
+
+    #[test]
+    #[should_panic(expected = "Combined probability of X and Y cannot exceed 1.0")]
+    fn test_random_move_new_panics() {
+        RandomMove::new(0.75, 0.5);
+    }
+}